@@ -1,16 +0,0 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-pub fn read_file_to_vec(path: &str) -> Result<Vec<String>, std::io::Error> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    reader.lines().collect()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {}
-}
@@ -0,0 +1,94 @@
+use aoc_library::{checked, Day, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 1;
+
+    type Input = Vec<String>;
+
+    fn parse(lines: Vec<String>) -> Vec<String> {
+        lines
+    }
+
+    fn part_one(input: &Vec<String>) -> Option<u64> {
+        let mut strings = input.clone();
+        remove_letters(&mut strings);
+        Some(sum_strings(&strings))
+    }
+
+    fn part_two(input: &Vec<String>) -> Option<u64> {
+        let mut strings = input.clone();
+        replace_spelled_numbers(&mut strings);
+        remove_letters(&mut strings);
+        Some(sum_strings(&strings))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day1>(env!("CARGO_MANIFEST_DIR"));
+
+aoc_library::aoc_examples!(DAY);
+
+pub fn replace_spelled_numbers(input: &mut [String]) {
+    for c in input {
+        *c = c
+            .replace("one", "o1e")
+            .replace("two", "t2o")
+            .replace("three", "t3e")
+            .replace("four", "f4r")
+            .replace("five", "f5e")
+            .replace("six", "s6x")
+            .replace("seven", "s7n")
+            .replace("eight", "e8t")
+            .replace("nine", "n9e")
+            .replace("zero", "z0o");
+    }
+}
+
+pub fn remove_letters(strings: &mut [String]) {
+    for c in strings {
+        c.retain(|c| c.is_ascii_digit());
+    }
+}
+
+pub fn sum_strings(strings: &[String]) -> u64 {
+    checked::sum(
+        strings
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (checked::Label("line", i + 1), get_string_value(c))),
+    )
+}
+
+pub fn get_string_value(string: &str) -> u64 {
+    let first = string.chars().next().unwrap().to_digit(10).unwrap();
+    let last = string.chars().next_back().unwrap().to_digit(10).unwrap();
+    (first * 10 + last).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calibrate(line: &str) -> u64 {
+        let mut strings = vec![line.to_string()];
+        replace_spelled_numbers(&mut strings);
+        remove_letters(&mut strings);
+        get_string_value(&strings[0])
+    }
+
+    #[test]
+    fn test_get_string_value() {
+        assert_eq!(get_string_value("7"), 77);
+        assert_eq!(get_string_value("1234"), 14);
+    }
+
+    #[test]
+    fn test_calibration_with_multi_byte_characters() {
+        assert_eq!(calibrate("é1ü"), 11);
+        assert_eq!(calibrate("ñtwo→3ß"), 23);
+        assert_eq!(calibrate("日本eightwo日本"), 82);
+        assert_eq!(calibrate("🎄5🎁nine"), 59);
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    aoc_library::examples::generate_tests();
+}
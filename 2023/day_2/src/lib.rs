@@ -1,164 +1,182 @@
-use aoc_library::read_file_to_vec;
+use aoc_library::counter::Counter;
+use aoc_library::params::{self, Param};
+use aoc_library::parse::{self, FromField, FromLine};
+use aoc_library::{checked, Day, Error, Solution};
 
-#[derive(Debug, Clone, Default)]
+/// The bag's contents for part one unless the `red`, `green` and `blue`
+/// parameters say otherwise.
+pub const CUBE_LIMIT: [(Color, u64); 3] = [(Color::Red, 12), (Color::Green, 13), (Color::Blue, 14)];
+
+pub struct Day2;
+
+/// The parameter named after `color` that overrides its limit.
+const fn limit_param((color, limit): (Color, u64)) -> Param {
+    Param::new(color.name(), limit as i64, limit as i64)
+}
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
+
+    // The example uses the same bag as the real puzzle.
+    const PARAMS: &'static [Param] = &[
+        limit_param(CUBE_LIMIT[0]),
+        limit_param(CUBE_LIMIT[1]),
+        limit_param(CUBE_LIMIT[2]),
+    ];
+
+    type Input = (Vec<Game>, BagPull);
+
+    fn parse(lines: Vec<String>) -> (Vec<Game>, BagPull) {
+        (process_all_lines_into_games(&lines), cube_limit())
+    }
+
+    fn part_one((games, limit): &(Vec<Game>, BagPull)) -> Option<u64> {
+        Some(sum_possible_games(games, limit))
+    }
+
+    fn part_two((games, _): &(Vec<Game>, BagPull)) -> Option<u64> {
+        Some(sum_of_powers_of_games(games))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day2>(env!("CARGO_MANIFEST_DIR"));
+
+aoc_library::aoc_examples!(DAY);
+
+#[derive(Debug, Clone, Default, FromLine)]
+#[aoc(pattern = "Game {number}: {pulls}")]
 pub struct Game {
     pub number: u32,
+    #[aoc(sep = ";", sep = ",")]
     pub pulls: Vec<BagPull>,
 }
 
 impl Game {
     pub fn is_possible_by_limit(&self, limit: &BagPull) -> bool {
-        for pull in self.pulls.iter() {
-            if pull.red > limit.red || pull.green > limit.green || pull.blue > limit.blue {
-                return false;
-            }
-        }
-        true
+        self.pulls.iter().all(|pull| pull.0.is_subset(&limit.0))
     }
 
     pub fn fewest_cubes_needed(&self) -> BagPull {
-        let mut max_colors = BagPull::default();
-
-        for pull in self.pulls.iter() {
-            if pull.red > max_colors.red {
-                max_colors.red = pull.red;
-            }
-            if pull.green > max_colors.green {
-                max_colors.green = pull.green;
-            }
-            if pull.blue > max_colors.blue {
-                max_colors.blue = pull.blue;
-            }
-        }
-
-        max_colors
+        let most = self
+            .pulls
+            .iter()
+            .fold(Counter::new(), |most, pull| most.max(&pull.0));
+        BagPull(most)
     }
 }
 
-pub fn power_of_set(set: &BagPull) -> u32 {
-    set.red * set.green * set.blue
+pub fn power_of_set(set: &BagPull) -> u64 {
+    checked::product(Color::ALL.map(|color| (checked::Label("cubes of", color), set.get(color))))
 }
 
-pub fn sum_of_powers_of_games(games: &[Game]) -> u32 {
-    let mut sum = 0;
-    for game in games.iter() {
+pub fn sum_of_powers_of_games(games: &[Game]) -> u64 {
+    checked::sum(games.iter().map(|game| {
         let power = power_of_set(&game.fewest_cubes_needed());
-        sum += power;
-    }
-    sum
+        (checked::Label("game", game.number), power)
+    }))
 }
 
-#[derive(Debug, Clone, Copy, Default)]
-pub struct BagPull {
-    pub red: u32,
-    pub green: u32,
-    pub blue: u32,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
 }
 
-pub fn play_games(limit: &BagPull) -> String {
-    let mut strings: Vec<String> = Vec::new();
-    match read_file_to_vec("input.txt") {
-        Ok(strs) => strings = strs,
-        Err(error) => print!("{}", error),
-    };
-
-    let mut results = String::from("Sum of possible games ");
-
-    let games = process_all_lines_into_games(&strings);
-    let sum = sum_possible_games(&games, limit);
-    results.push_str(sum.to_string().as_str());
+impl Color {
+    pub const ALL: [Color; 3] = [Color::Red, Color::Green, Color::Blue];
 
-    let sum_of_powers = sum_of_powers_of_games(&games);
-
-    results.push_str("\nSum of powers of games ");
-    results.push_str(sum_of_powers.to_string().as_str());
-
-    results
-}
-
-pub fn sum_possible_games(games: &[Game], limit: &BagPull) -> u32 {
-    let mut sum = 0;
-    for game in games.iter() {
-        if game.is_possible_by_limit(limit) {
-            sum += game.number;
+    pub const fn name(self) -> &'static str {
+        match self {
+            Color::Red => "red",
+            Color::Green => "green",
+            Color::Blue => "blue",
         }
     }
-    sum
 }
 
-pub fn process_all_lines_into_games(lines: &[String]) -> Vec<Game> {
-    let mut games: Vec<Game> = Vec::new();
-    for line in lines.iter() {
-        let game = process_line_into_game(line);
-        games.push(game);
+impl FromField for Color {
+    fn from_field(text: &str, _separators: &[&str]) -> Result<Color, Error> {
+        Color::ALL
+            .into_iter()
+            .find(|color| color.name() == text)
+            .ok_or_else(|| Error::new(1, format!("unknown color {text:?}")))
     }
-    games
 }
 
-pub fn process_line_into_game(line: &str) -> Game {
-    let (game, pulls) = split_into_game_and_pulls(line);
-    let game_number = extract_game_number(game);
-    let pulls = extract_pulls(pulls);
-    let pull_vec = build_bag_pulls_vec(&pulls);
+/// Cubes counted by color: one pull, or what the bag holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BagPull(pub Counter<Color>);
+
+impl BagPull {
+    pub fn new(red: u64, green: u64, blue: u64) -> BagPull {
+        BagPull(Counter::from_counts([
+            (Color::Red, red),
+            (Color::Green, green),
+            (Color::Blue, blue),
+        ]))
+    }
 
-    Game {
-        number: game_number,
-        pulls: pull_vec,
+    pub fn get(&self, color: Color) -> u64 {
+        self.0.get(&color)
     }
 }
 
-pub fn split_into_game_and_pulls(line: &str) -> (&str, &str) {
-    let game = line.split(':').next().unwrap();
-    let pulls = line.split(':').last().unwrap();
-    (game, pulls)
+/// The limit for part one from the current parameters.
+pub fn cube_limit() -> BagPull {
+    BagPull(Counter::from_counts(CUBE_LIMIT.map(|(color, count)| {
+        (color, params::get_or(color.name(), count))
+    })))
 }
 
-pub fn extract_game_number(line: &str) -> u32 {
-    if let Some(num) = line.split(' ').last() {
-        num.parse::<u32>().unwrap()
-    } else {
-        0
-    }
+pub fn sum_possible_games(games: &[Game], limit: &BagPull) -> u64 {
+    checked::sum(
+        games
+            .iter()
+            .filter(|game| game.is_possible_by_limit(limit))
+            .map(|game| (checked::Label("game", game.number), u64::from(game.number))),
+    )
 }
 
-pub fn extract_pulls(line: &str) -> Vec<&str> {
-    line.split(';').collect()
+pub fn process_all_lines_into_games(lines: &[String]) -> Vec<Game> {
+    parse::lines(lines).unwrap_or_else(|error| panic!("{error}"))
 }
 
-pub fn build_bag_pulls_vec(pulls: &[&str]) -> Vec<BagPull> {
-    let mut pulls_vec: Vec<BagPull> = Vec::new();
-    for pull in pulls.iter() {
-        let cubes = extract_cube_count(pull);
-        pulls_vec.push(cubes);
-    }
-
-    pulls_vec
+/// One color's count within a pull, e.g. `4 red`.
+#[derive(FromLine)]
+#[aoc(pattern = "{count} {color}")]
+struct Cubes {
+    count: u64,
+    color: Color,
 }
 
-pub fn extract_cube_count(line: &str) -> BagPull {
-    let mut colors: Vec<&str> = line.split(',').collect();
-    let mut pull = BagPull::default();
-
-    for color in colors.iter_mut() {
-        *color = color.trim();
-        let color_split: Vec<&str> = color.split(' ').collect();
-        let color_count = color_split[0].parse::<u32>().unwrap();
-        let color_name = color_split[1];
-
-        match color_name {
-            "red" => pull.red = color_count,
-            "green" => pull.green = color_count,
-            "blue" => pull.blue = color_count,
-            _ => (),
+/// A pull such as `4 red, 1 green`, with the separator between colors.
+/// Each color may be listed once; a repeat is reported at the pull.
+impl FromField for BagPull {
+    fn from_field(text: &str, separators: &[&str]) -> Result<BagPull, Error> {
+        let listed = Vec::<Cubes>::from_field(text, separators)?;
+        let mut pull = Counter::new();
+        for (index, cubes) in listed.iter().enumerate() {
+            if listed[..index]
+                .iter()
+                .any(|earlier| earlier.color == cubes.color)
+            {
+                return Err(Error::new(
+                    1,
+                    format!("{} listed twice", cubes.color.name()),
+                ));
+            }
+            pull.add_n(cubes.color, cubes.count);
         }
+        Ok(BagPull(pull))
     }
-
-    pull
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_library::read_file_to_vec;
 
     #[test]
     fn test_read_file_to_vec() {
@@ -171,71 +189,84 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_game_number() {
-        let game_1 = extract_game_number("Game 1");
-        let game_22 = extract_game_number("Game 22");
-        let game_100 = extract_game_number("blah blah blah 100");
+    fn test_game_number() {
+        let game_1 = Game::from_line("Game 1: 1 red").unwrap();
+        let game_100 = Game::from_line("Game 100: 1 red").unwrap();
+
+        assert_eq!(game_1.number, 1);
+        assert_eq!(game_100.number, 100);
+    }
 
-        assert_eq!(game_1, 1);
-        assert_eq!(game_22, 22);
-        assert_eq!(game_100, 100);
+    #[test]
+    fn test_cubes_have_all_colors() {
+        let cubes = BagPull::from_field("4 red, 1 green, 15 blue", &[","]).unwrap();
+        assert_eq!(cubes.get(Color::Red), 4);
+        assert_eq!(cubes.get(Color::Green), 1);
+        assert_eq!(cubes.get(Color::Blue), 15);
     }
 
     #[test]
-    fn test_extract_pulls_returns_vec_of_bag_pulls() {
-        let string =
-            "4 red, 1 green, 15 blue; 6 green, 2 red, 10 blue; 7 blue, 6 green, 4 red; 12 blue, 10 green, 3 red";
-        let pulls = extract_pulls(string);
-        assert_eq!(pulls[0], "4 red, 1 green, 15 blue");
+    fn test_cubes_have_one_color() {
+        let cubes = BagPull::from_field("1 red", &[","]).unwrap();
+        assert_eq!(cubes.get(Color::Red), 1);
+        assert_eq!(cubes.get(Color::Green), 0);
+        assert_eq!(cubes.get(Color::Blue), 0);
     }
 
     #[test]
-    fn test_extract_cubes_has_all_colors() {
-        let string = "4 red, 1 green, 15 blue";
-        let cubes: BagPull = extract_cube_count(string);
-        assert_eq!(cubes.red, 4);
-        assert_eq!(cubes.green, 1);
-        assert_eq!(cubes.blue, 15);
+    fn test_repeated_color_is_reported() {
+        let error = BagPull::from_field("4 red, 1 green, 2 red", &[","]).unwrap_err();
+        assert_eq!(error.to_string(), "column 1: red listed twice");
+        let error = Game::from_line("Game 3: 4 red; 2 blue, 0 blue").unwrap_err();
+        assert_eq!(error.to_string(), "column 16: blue listed twice");
     }
 
     #[test]
-    fn test_extract_cubes_has_one_color() {
-        let string = "1 red";
-        let cubes: BagPull = extract_cube_count(string);
-        assert_eq!(cubes.red, 1);
-        assert_eq!(cubes.green, 0);
-        assert_eq!(cubes.blue, 0);
+    fn test_unknown_color_is_reported() {
+        let error = Game::from_line("Game 3: 4 red; 2 purple").unwrap_err();
+        assert_eq!(error.to_string(), "column 18: unknown color \"purple\"");
     }
 
     #[test]
     fn test_process_line_returns_game() {
         let line = "Game 1: 4 red, 1 green, 15 blue; 6 green, 2 red, 10 blue; 7 blue, 6 green, 4 red; 12 blue, 10 green, 3 red";
-        let limit = BagPull {
-            red: 12,
-            green: 13,
-            blue: 14,
-        };
-        let game = process_line_into_game(line);
+        let limit = BagPull::new(12, 13, 14);
+        let game = Game::from_line(line).unwrap();
         assert_eq!(game.number, 1);
-        assert!(game.is_possible_by_limit(&limit));
-        assert_eq!(game.pulls[0].red, 4);
-        assert_eq!(game.pulls[0].green, 1);
-        assert_eq!(game.pulls[0].blue, 15);
-        assert_eq!(game.pulls[3].red, 3);
-        assert_eq!(game.pulls[3].green, 10);
-        assert_eq!(game.pulls[3].blue, 12);
+        // 15 blue in the first pull exceeds the limit of 14.
+        assert!(!game.is_possible_by_limit(&limit));
+        assert_eq!(game.pulls[0].get(Color::Red), 4);
+        assert_eq!(game.pulls[0].get(Color::Green), 1);
+        assert_eq!(game.pulls[0].get(Color::Blue), 15);
+        assert_eq!(game.pulls[3].get(Color::Red), 3);
+        assert_eq!(game.pulls[3].get(Color::Green), 10);
+        assert_eq!(game.pulls[3].get(Color::Blue), 12);
         assert_eq!(game.pulls.len(), 4);
     }
 
+    #[test]
+    fn test_cube_limit_from_params() {
+        assert_eq!(cube_limit(), BagPull::new(12, 13, 14));
+        let red = params::Params::from([("red".to_string(), 20)]);
+        let limit = params::with_params(&red, cube_limit);
+        assert_eq!(limit.get(Color::Red), 20);
+        assert_eq!(limit.get(Color::Blue), 14);
+    }
+
+    #[test]
+    fn test_fewest_cubes_and_power() {
+        let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        let game = Game::from_line(line).unwrap();
+        assert_eq!(game.fewest_cubes_needed(), BagPull::new(4, 2, 6));
+        assert_eq!(power_of_set(&game.fewest_cubes_needed()), 48);
+        assert_eq!(power_of_set(&BagPull::new(4, 0, 6)), 0);
+    }
+
     #[test]
     fn test_impossible_game() {
         let line = "Game 1: 4 red, 1 green, 15 blue; 6 green, 2 red, 10 blue; 7 blue, 6 green, 4 red; 12 blue, 10 green, 3 red";
-        let limit = BagPull {
-            red: 3,
-            green: 3,
-            blue: 3,
-        };
-        let game = process_line_into_game(line);
+        let limit = BagPull::new(3, 3, 3);
+        let game = Game::from_line(line).unwrap();
 
         game.is_possible_by_limit(&limit);
         assert!(!game.is_possible_by_limit(&limit));
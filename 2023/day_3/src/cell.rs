@@ -0,0 +1,12 @@
+use aoc_library::grid::GridCell;
+
+/// One character of the schematic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+pub enum Cell {
+    #[cell('.')]
+    Empty,
+    #[cell('0'..='9')]
+    Digit(char),
+    #[cell('*', '#', '%', '-', '$', '@', '/', '&', '=', '+')]
+    Symbol(char),
+}
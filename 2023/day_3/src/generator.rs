@@ -0,0 +1,170 @@
+//! Seeded random schematics for property tests.
+
+pub const SYMBOLS: [char; 10] = ['*', '#', '%', '-', '$', '@', '/', '&', '=', '+'];
+
+/// Small xorshift generator so schematics are reproducible from a seed.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift never leaves the all-zero state, so mix the seed first.
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() % 10_000) as f64 / 10_000.0 < probability
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GeneratorConfig {
+    pub width: usize,
+    pub height: usize,
+    /// Chance that any free cell starts a number.
+    pub number_density: f64,
+    /// Chance that any free cell holds a symbol.
+    pub symbol_density: f64,
+    /// Number of attempts at a `*` with two to four numbers written around
+    /// it. An attempt on a taken cell places nothing, and numbers that run
+    /// into others are shortened or dropped, so a `*` can end up next to
+    /// fewer than two numbers.
+    pub gear_clusters: usize,
+    /// Force numbers onto the first and last rows and columns.
+    pub edge_numbers: bool,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        GeneratorConfig {
+            width: 20,
+            height: 12,
+            number_density: 0.08,
+            symbol_density: 0.04,
+            gear_clusters: 3,
+            edge_numbers: true,
+        }
+    }
+}
+
+pub fn generate(seed: u64, config: &GeneratorConfig) -> Vec<String> {
+    let mut rng = Rng::new(seed);
+    let mut grid = vec![vec!['.'; config.width]; config.height];
+
+    if config.edge_numbers {
+        place_edge_numbers(&mut grid, &mut rng);
+    }
+
+    for _ in 0..config.gear_clusters {
+        place_gear_cluster(&mut grid, &mut rng);
+    }
+
+    for y in 0..config.height {
+        for x in 0..config.width {
+            if rng.chance(config.number_density) {
+                let digits = 1 + rng.below(3);
+                place_number(&mut grid, &mut rng, x, y, digits);
+            } else if rng.chance(config.symbol_density) && grid[y][x] == '.' {
+                grid[y][x] = SYMBOLS[rng.below(SYMBOLS.len())];
+            }
+        }
+    }
+
+    grid.into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect()
+}
+
+/// Writes a number of up to `digits` digits starting at `(x, y)`, clipped
+/// to the free cells of the row. Numbers never run into existing digits, so
+/// every number in the output has at most three digits.
+fn place_number(grid: &mut [Vec<char>], rng: &mut Rng, x: usize, y: usize, digits: usize) {
+    let row = &mut grid[y];
+    let is_digit = |cell: Option<&char>| cell.is_some_and(char::is_ascii_digit);
+    if x > 0 && is_digit(row.get(x - 1)) {
+        return;
+    }
+
+    let mut length = 0;
+    while length < digits && row.get(x + length) == Some(&'.') {
+        length += 1;
+    }
+    if is_digit(row.get(x + length)) {
+        length = length.saturating_sub(1);
+    }
+
+    for offset in 0..length {
+        let digit = if offset == 0 {
+            1 + rng.below(9)
+        } else {
+            rng.below(10)
+        };
+        row[x + offset] = char::from_digit(digit as u32, 10).unwrap();
+    }
+}
+
+fn place_edge_numbers(grid: &mut [Vec<char>], rng: &mut Rng) {
+    let height = grid.len();
+    let width = grid.first().map_or(0, Vec::len);
+    if width < 3 || height < 2 {
+        return;
+    }
+
+    // Left edge, right edge, and one number on each of the top and bottom rows.
+    let spots = [
+        (0, rng.below(height)),
+        (width, rng.below(height)),
+        (rng.below(width), 0),
+        (rng.below(width), height - 1),
+    ];
+    for (x, y) in spots {
+        let digits = 1 + rng.below(3);
+        // `width` marks a number that should end on the last column.
+        let x = if x == width { width - digits } else { x };
+        place_number(grid, rng, x, y, digits);
+    }
+}
+
+/// Places a `*` at a random free cell and tries to write two to four
+/// numbers around it, sometimes adding a second `*` that may share one.
+fn place_gear_cluster(grid: &mut [Vec<char>], rng: &mut Rng) {
+    let height = grid.len();
+    let width = grid.first().map_or(0, Vec::len);
+    if width < 3 || height < 3 {
+        return;
+    }
+
+    let x = rng.below(width);
+    let y = rng.below(height);
+    if grid[y][x] != '.' {
+        return;
+    }
+    grid[y][x] = '*';
+
+    let neighbours = 2 + rng.below(3);
+    for _ in 0..neighbours {
+        let ny = (y + rng.below(3)).saturating_sub(1).min(height - 1);
+        let nx = (x + rng.below(3)).saturating_sub(1).min(width - 1);
+        let digits = 1 + rng.below(3);
+        // Start far enough left that the number still touches the gear.
+        let start = nx.saturating_sub(rng.below(digits));
+        place_number(grid, rng, start, ny, digits);
+    }
+
+    if rng.chance(0.5) && x + 4 < width {
+        let second = x + 2 + rng.below(3);
+        if grid[y][second] == '.' {
+            grid[y][second] = '*';
+        }
+    }
+}
@@ -1,3 +1,37 @@
+pub mod cell;
 pub mod number;
 pub mod schematic;
 pub mod symbol;
+
+#[cfg(test)]
+mod generator;
+#[cfg(test)]
+mod oracle;
+
+use aoc_library::{Day, Solution};
+use schematic::Schematic;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 3;
+
+    type Input = Schematic;
+
+    fn parse(lines: Vec<String>) -> Schematic {
+        Schematic::new(lines).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    fn part_one(schematic: &Schematic) -> Option<u64> {
+        Some(schematic.part_number_sum())
+    }
+
+    fn part_two(schematic: &Schematic) -> Option<u64> {
+        Some(schematic.gear_ratio_sum())
+    }
+}
+
+pub const DAY: Day = Day::new::<Day3>(env!("CARGO_MANIFEST_DIR"));
+
+aoc_library::aoc_examples!(DAY);
@@ -1,7 +1,5 @@
-use day_3::schematic::process_input;
+use std::process::ExitCode;
 
-fn main() {
-    let schematic = process_input("./2023/day_3/input.txt");
-    println!("Sum of part numbers: {}", schematic.part_number_sum());
-    println!("Sum of gear ratios: {}", schematic.gear_ratio_sum());
+fn main() -> ExitCode {
+    aoc_library::run::main(&aoc_2023_day_3::DAY)
 }
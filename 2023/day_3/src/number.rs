@@ -2,5 +2,5 @@
 pub struct Number {
     pub pos: (u32, u32),
     pub length: u32,
-    pub value: u32,
+    pub value: u64,
 }
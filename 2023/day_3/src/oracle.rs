@@ -0,0 +1,109 @@
+//! Deliberately naive reference solver used to check `Schematic` in tests.
+//!
+//! Works directly on the character grid and checks every neighbouring cell
+//! of every digit, so it shares no logic with the real implementation.
+
+struct Run {
+    y: usize,
+    start: usize,
+    end: usize,
+    value: u64,
+}
+
+fn grid(lines: &[String]) -> Vec<Vec<char>> {
+    lines.iter().map(|line| line.chars().collect()).collect()
+}
+
+fn runs(grid: &[Vec<char>]) -> Vec<Run> {
+    let mut runs = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            if !row[x].is_ascii_digit() {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            let mut value = 0;
+            while x < row.len() && row[x].is_ascii_digit() {
+                value = value * 10 + u64::from(row[x].to_digit(10).unwrap());
+                x += 1;
+            }
+            runs.push(Run {
+                y,
+                start,
+                end: x - 1,
+                value,
+            });
+        }
+    }
+    runs
+}
+
+fn neighbours(grid: &[Vec<char>], x: usize, y: usize) -> Vec<(usize, usize)> {
+    let mut cells = Vec::new();
+    for dy in -1_i64..=1 {
+        for dx in -1_i64..=1 {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+            let nx = x as i64 + dx;
+            let ny = y as i64 + dy;
+            if ny < 0
+                || nx < 0
+                || ny as usize >= grid.len()
+                || nx as usize >= grid[ny as usize].len()
+            {
+                continue;
+            }
+            cells.push((nx as usize, ny as usize));
+        }
+    }
+    cells
+}
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+pub fn part_number_sum(lines: &[String]) -> u64 {
+    let grid = grid(lines);
+    let mut sum = 0;
+    for run in runs(&grid) {
+        let touches_symbol = (run.start..=run.end).any(|x| {
+            neighbours(&grid, x, run.y)
+                .into_iter()
+                .any(|(nx, ny)| is_symbol(grid[ny][nx]))
+        });
+        if touches_symbol {
+            sum += run.value;
+        }
+    }
+    sum
+}
+
+pub fn gear_ratio_sum(lines: &[String]) -> u64 {
+    let grid = grid(lines);
+    let runs = runs(&grid);
+    let mut sum = 0;
+    for (y, row) in grid.iter().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            if *c != '*' {
+                continue;
+            }
+            let cells = neighbours(&grid, x, y);
+            let adjacent: Vec<&Run> = runs
+                .iter()
+                .filter(|run| {
+                    cells
+                        .iter()
+                        .any(|&(nx, ny)| ny == run.y && nx >= run.start && nx <= run.end)
+                })
+                .collect();
+            if adjacent.len() == 2 {
+                sum += adjacent[0].value * adjacent[1].value;
+            }
+        }
+    }
+    sum
+}
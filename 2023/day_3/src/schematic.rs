@@ -1,113 +1,126 @@
 use crate::{
+    cell::Cell,
     number::Number,
     symbol::{Gear, Symbol},
 };
-use aoc_library::read_file_to_vec;
+use aoc_library::grid::{Grid, GridCell, GridError};
+use aoc_library::{checked, read_file_to_vec};
 
-#[derive(Default)]
+/// A schematic is a grid of cells, each one ASCII character: columns are
+/// byte offsets into each line, so every line must be the same length and
+/// hold only digits, periods and symbols. Both are checked when the
+/// schematic is loaded.
 pub struct Schematic {
-    contents: Vec<String>,
+    grid: Grid<Cell>,
     part_numbers: Vec<Number>,
     all_numbers: Vec<Number>,
     all_symbols: Vec<Symbol>,
-    size_x: u32,
-    size_y: u32,
 }
 
+/// Loading errors are the grid's: unknown characters and ragged lines.
+pub type SchematicError = GridError;
+
 pub fn process_input(path: &str) -> Schematic {
     let contents = read_file_to_vec(path).unwrap();
-    Schematic::new(contents)
+    Schematic::new(contents).unwrap_or_else(|error| panic!("{path}: {error}"))
 }
 
 impl Schematic {
-    pub fn new(contents: Vec<String>) -> Self {
-        let mut schematic: Schematic = Schematic {
-            contents,
-            ..Default::default()
+    pub fn new(contents: Vec<String>) -> Result<Self, SchematicError> {
+        let mut schematic = Schematic {
+            grid: Grid::parse(&contents)?,
+            part_numbers: Vec::new(),
+            all_numbers: Vec::new(),
+            all_symbols: Vec::new(),
         };
         schematic.process_contents();
-        schematic
+        Ok(schematic)
     }
 
-    pub fn part_number_sum(&self) -> u32 {
-        self.part_numbers.iter().map(|num| num.value).sum()
+    pub fn part_number_sum(&self) -> u64 {
+        checked::sum(
+            self.part_numbers
+                .iter()
+                .map(|num| (checked::Label("part number at", num.pos), num.value)),
+        )
     }
 
     pub fn line_length(&self) -> u32 {
-        let len = if let Some(string) = self.contents.first() {
-            string.len()
-        } else {
-            0
-        };
-
-        len.try_into().unwrap()
+        self.grid.width()
     }
 
     pub fn file_size(&self) -> u32 {
-        self.contents.len().try_into().unwrap()
+        self.grid.height()
     }
 
     pub fn process_contents(&mut self) {
-        self.size_x = self.line_length();
-        self.size_y = self.file_size();
-
-        for (y, line) in self.contents.iter().enumerate() {
-            let y: u32 = y.try_into().unwrap();
-
-            let mut numbers = Schematic::extract_numbers_from_string(line, y);
+        for (y, row) in (0_u32..).zip(self.grid.rows()) {
+            let mut numbers = Schematic::extract_numbers(row.iter().map(Cell::to_char), y);
             self.all_numbers.append(&mut numbers);
 
-            let mut symbols = Schematic::extract_symbols_from_string(line, y);
+            let mut symbols = Schematic::extract_symbols(row.iter().copied(), y);
             self.all_symbols.append(&mut symbols);
         }
 
         self.part_numbers.append(&mut self.scan_for_part_numbers());
     }
 
+    /// Symbols in `string`, positioned by byte offset. Bytes that are not
+    /// cells count as empty.
+    #[cfg(test)]
     fn extract_symbols_from_string(string: &str, y: u32) -> Vec<Symbol> {
+        let cells = string
+            .bytes()
+            .map(|byte| Cell::from_char(char::from(byte)).unwrap_or(Cell::Empty));
+        Schematic::extract_symbols(cells, y)
+    }
+
+    /// Numbers in `string`, positioned by byte offset.
+    #[cfg(test)]
+    fn extract_numbers_from_string(string: &str, line: u32) -> Vec<Number> {
+        Schematic::extract_numbers(string.bytes().map(char::from), line)
+    }
+
+    fn extract_symbols(row: impl Iterator<Item = Cell>, y: u32) -> Vec<Symbol> {
         let mut symbols = Vec::new();
 
-        for (index, c) in (0_u32..).zip(string.chars()) {
-            let symbol = match c {
-                '*' | '#' | '%' | '-' | '$' | '@' | '/' | '&' | '=' | '+' => Symbol {
+        for (index, cell) in (0_u32..).zip(row) {
+            if let Cell::Symbol(symbol) = cell {
+                symbols.push(Symbol {
                     pos: (index, y),
-                    symbol: c,
-                },
-                _ => continue,
-            };
-
-            symbols.push(symbol);
+                    symbol,
+                });
+            }
         }
 
         symbols
     }
 
-    fn extract_numbers_from_string(string: &str, line: u32) -> Vec<Number> {
+    fn extract_numbers(row: impl Iterator<Item = char>, line: u32) -> Vec<Number> {
         let mut number = Number::default();
         let mut numbers = Vec::new();
-        let mut current_number: u32 = 0;
+        let mut current_number: u64 = 0;
         let mut num_found: bool = false;
 
-        for (index, c) in (0_u32..).zip(string.chars()) {
+        let mut row = (0_u32..).zip(row).peekable();
+        while let Some((index, c)) = row.next() {
             if c.is_ascii_digit() {
                 num_found = true;
-                let (x, y) = &mut number.pos;
-                if *x == 0 {
-                    *x = index
+                if number.length == 0 {
+                    number.pos = (index, line);
                 }
 
-                *y = line;
                 number.length += 1;
 
-                if current_number == 0 {
-                    current_number = c.to_digit(10).unwrap();
-                } else {
-                    current_number *= 10;
-                    current_number += c.to_digit(10).unwrap();
-                }
+                current_number = current_number
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(c.to_digit(10).unwrap().into()))
+                    .unwrap_or_else(|| {
+                        panic!("u64 overflow at number starting at {:?}", number.pos)
+                    });
             }
 
-            let last_char_was_number = index == string.len() as u32 - 1 && num_found;
+            let last_char_was_number = row.peek().is_none() && num_found;
             let end_of_number_found = num_found && !c.is_ascii_digit();
 
             if end_of_number_found || last_char_was_number {
@@ -122,29 +135,33 @@ impl Schematic {
     }
 
     pub fn get_char_at_coord(&self, x: u32, y: u32) -> Option<char> {
-        if x >= self.size_x || y >= self.size_y {
-            return None;
-        }
+        self.grid.get((x, y)).map(Cell::to_char)
+    }
 
-        self.contents[y as usize].chars().nth(x as usize)
+    #[cfg(not(feature = "parallel"))]
+    pub fn scan_for_part_numbers(&self) -> Vec<Number> {
+        self.part_numbers_in(&self.all_numbers)
     }
 
+    /// Scans the numbers in one chunk per core.
+    #[cfg(feature = "parallel")]
     pub fn scan_for_part_numbers(&self) -> Vec<Number> {
-        let mut part_numbers = Vec::new();
-        for number in &self.all_numbers {
-            if self
-                .all_symbols
-                .iter()
-                .any(|symbol| self.symbol_is_next_to_number(symbol, number))
-            {
-                println!("Found part number: {:?}", number);
-                part_numbers.push(*number);
-            } else {
-                println!("{:?} Is not a part number", number)
-            }
-        }
+        aoc_library::parallel::map_chunks(&self.all_numbers, |numbers| {
+            self.part_numbers_in(numbers)
+        })
+        .concat()
+    }
 
-        part_numbers
+    fn part_numbers_in(&self, numbers: &[Number]) -> Vec<Number> {
+        numbers
+            .iter()
+            .filter(|number| {
+                self.all_symbols
+                    .iter()
+                    .any(|symbol| self.symbol_is_next_to_number(symbol, number))
+            })
+            .copied()
+            .collect()
     }
 
     fn symbol_is_next_to_number(&self, symbol: &Symbol, number: &Number) -> bool {
@@ -182,26 +199,239 @@ impl Schematic {
         gears
     }
 
-    pub fn gear_ratio_sum(&self) -> u32 {
-        let mut sum = 0;
-        for gear in self.filter_for_gear_ratios() {
-            sum += gear.ratio();
-        }
-        sum
+    pub fn gear_ratio_sum(&self) -> u64 {
+        checked::sum(
+            self.filter_for_gear_ratios()
+                .iter()
+                .map(|gear| (checked::Label("gear at", gear.symbol.pos), gear.ratio())),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::schematic;
-
     use super::*;
+    use crate::generator::{self, GeneratorConfig};
+    use crate::oracle;
+
+    fn lines(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn test_scan_current_line_for_symbol() {
+        let schematic = Schematic::new(lines(&["12*..34..", ".........", "..5#....."])).unwrap();
+        assert_eq!(schematic.part_number_sum(), 17);
+
+        let schematic = Schematic::new(lines(&["....*", "12..7"])).unwrap();
+        assert_eq!(schematic.part_number_sum(), 7);
+    }
+
+    #[test]
+    fn test_scan_next_line_for_symbol() {
+        let schematic =
+            Schematic::new(lines(&["12...34", "..$..@.", ".......", "9......"])).unwrap();
+        assert_eq!(schematic.part_number_sum(), 46);
+
+        let schematic = Schematic::new(lines(&["......", "...#..", "99...."])).unwrap();
+        assert_eq!(schematic.part_number_sum(), 0);
+    }
+
+    #[test]
+    fn test_number_at_start_of_line_keeps_its_column() {
+        let numbers = Schematic::extract_numbers_from_string("467..114..", 3);
+        assert_eq!(numbers[0].pos, (0, 3));
+        assert_eq!(numbers[0].length, 3);
+        assert_eq!(numbers[1].pos, (5, 3));
+    }
+
+    #[test]
+    fn test_gear_needs_exactly_two_numbers() {
+        let schematic = Schematic::new(lines(&["2.3", ".*.", "4.."])).unwrap();
+        assert_eq!(schematic.gear_ratio_sum(), 0);
+
+        let schematic = Schematic::new(lines(&["...", ".*.", "..."])).unwrap();
+        assert_eq!(schematic.gear_ratio_sum(), 0);
+
+        let schematic = Schematic::new(lines(&["2..", ".*.", "..7"])).unwrap();
+        assert_eq!(schematic.gear_ratio_sum(), 14);
+    }
+
+    #[test]
+    fn test_wide_numbers_do_not_wrap() {
+        let numbers = Schematic::extract_numbers_from_string("..98765432109..", 0);
+        assert_eq!(numbers[0].value, 98_765_432_109);
+
+        let schematic = Schematic::new(lines(&["999999*999999"])).unwrap();
+        assert_eq!(schematic.gear_ratio_sum(), 999_998_000_001);
+    }
+
+    #[test]
+    #[should_panic(expected = "u64 overflow at number starting at (1, 0)")]
+    fn test_overflowing_number_is_reported() {
+        Schematic::extract_numbers_from_string(".123456789012345678901", 0);
+    }
+
+    #[test]
+    fn test_multi_byte_character_is_rejected_with_position() {
+        let error = Schematic::new(lines(&["12..", ".é*.", "..3."])).err();
+        assert_eq!(
+            error,
+            Some(SchematicError::UnknownCell {
+                pos: (1, 1),
+                found: 'é',
+                expected: Cell::CHARS,
+            })
+        );
+        assert_eq!(
+            error.unwrap().to_string(),
+            "unknown character 'é' at line 2, column 2, expected one of \".0123456789*#%-$@/&=+\""
+        );
+
+        // A multi-byte character after a number must not hide the number's end.
+        let error = Schematic::new(lines(&["..12", "*.3→"])).err();
+        assert_eq!(
+            error,
+            Some(SchematicError::UnknownCell {
+                pos: (3, 1),
+                found: '→',
+                expected: Cell::CHARS,
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_ascii_character_is_rejected() {
+        let error = Schematic::new(lines(&["12..", "..x."])).err();
+        assert!(matches!(
+            error,
+            Some(SchematicError::UnknownCell {
+                pos: (2, 1),
+                found: 'x',
+                ..
+            })
+        ));
+    }
 
     #[test]
-    fn test_scan_current_line_for_symbol() {}
+    fn test_ragged_lines_are_rejected() {
+        let error = Schematic::new(lines(&["12..", "...", "..3."])).err();
+        assert_eq!(
+            error,
+            Some(SchematicError::RaggedLine {
+                line: 1,
+                expected: 4,
+                found: 3,
+            })
+        );
+    }
 
     #[test]
-    fn test_scan_next_line_for_symbol() {}
+    fn test_extraction_columns_are_byte_offsets() {
+        let numbers = Schematic::extract_numbers_from_string("é12.34", 0);
+        assert_eq!(numbers.len(), 2);
+        assert_eq!(numbers[0].pos, (2, 0));
+        assert_eq!(numbers[1].pos, (5, 0));
+        assert_eq!(numbers[1].value, 34);
+
+        let symbols = Schematic::extract_symbols_from_string("→*", 0);
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].pos, (3, 0));
+    }
+
+    #[test]
+    fn test_example_schematic() {
+        let schematic = Schematic::new(lines(&[
+            "467..114..",
+            "...*......",
+            "..35..633.",
+            "......#...",
+            "617*......",
+            ".....+.58.",
+            "..592.....",
+            "......755.",
+            "...$.*....",
+            ".664.598..",
+        ]))
+        .unwrap();
+        assert_eq!(schematic.part_number_sum(), 4361);
+        assert_eq!(schematic.gear_ratio_sum(), 467835);
+    }
+
+    fn assert_matches_oracle(seed: u64, config: &GeneratorConfig) {
+        let input = generator::generate(seed, config);
+        let schematic = Schematic::new(input.clone()).unwrap();
+        assert_eq!(
+            schematic.part_number_sum(),
+            oracle::part_number_sum(&input),
+            "part number sum differs for seed {seed}:\n{}",
+            input.join("\n")
+        );
+        assert_eq!(
+            schematic.gear_ratio_sum(),
+            oracle::gear_ratio_sum(&input),
+            "gear ratio sum differs for seed {seed}:\n{}",
+            input.join("\n")
+        );
+    }
+
+    #[test]
+    fn test_generated_schematics_match_oracle() {
+        let config = GeneratorConfig::default();
+        for seed in 0..300 {
+            assert_matches_oracle(seed, &config);
+        }
+    }
+
+    #[test]
+    fn test_dense_generated_schematics_match_oracle() {
+        let config = GeneratorConfig {
+            number_density: 0.3,
+            symbol_density: 0.2,
+            gear_clusters: 8,
+            ..Default::default()
+        };
+        for seed in 0..300 {
+            assert_matches_oracle(seed, &config);
+        }
+    }
+
+    #[test]
+    fn test_sparse_and_tiny_generated_schematics_match_oracle() {
+        let sparse = GeneratorConfig {
+            width: 60,
+            height: 30,
+            number_density: 0.02,
+            symbol_density: 0.01,
+            gear_clusters: 1,
+            edge_numbers: false,
+        };
+        let tiny = GeneratorConfig {
+            width: 3,
+            height: 3,
+            number_density: 0.5,
+            symbol_density: 0.3,
+            gear_clusters: 1,
+            edge_numbers: true,
+        };
+        for seed in 0..300 {
+            assert_matches_oracle(seed, &sparse);
+            assert_matches_oracle(seed, &tiny);
+        }
+    }
+
+    #[test]
+    fn test_generator_is_deterministic() {
+        let config = GeneratorConfig::default();
+        assert_eq!(
+            generator::generate(7, &config),
+            generator::generate(7, &config)
+        );
+        assert_ne!(
+            generator::generate(7, &config),
+            generator::generate(8, &config)
+        );
+    }
 
     #[test]
     fn test_extract_symbols_from_string() {
@@ -1,3 +1,5 @@
+use aoc_library::checked::{Label, Overflow};
+
 use crate::number::Number;
 
 #[derive(Default, Debug, Clone)]
@@ -13,11 +15,18 @@ pub struct Gear {
 }
 
 impl Gear {
-    pub fn ratio(&self) -> u32 {
-        if self.gears.len() == 1 {
-            0
-        } else {
-            self.gears[0].value * self.gears[1].value
+    pub fn ratio(&self) -> u64 {
+        self.try_ratio()
+            .unwrap_or_else(|overflow| panic!("{overflow}"))
+    }
+
+    pub fn try_ratio(&self) -> Result<u64, Overflow> {
+        if self.gears.len() != 2 {
+            return Ok(0);
         }
+        self.gears[0]
+            .value
+            .checked_mul(self.gears[1].value)
+            .ok_or_else(|| Overflow::new(Label("gear at", self.symbol.pos)))
     }
 }
@@ -1 +1,25 @@
 pub mod scratch_card;
+
+use aoc_library::{Day, Solution};
+use scratch_card::{parse_input, total_winnings, ScratchCard};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
+
+    type Input = Vec<ScratchCard>;
+
+    fn parse(lines: Vec<String>) -> Vec<ScratchCard> {
+        parse_input(lines)
+    }
+
+    fn part_one(cards: &Vec<ScratchCard>) -> Option<u64> {
+        Some(total_winnings(cards))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day4>(env!("CARGO_MANIFEST_DIR"));
+
+aoc_library::aoc_examples!(DAY);
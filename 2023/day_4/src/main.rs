@@ -1,8 +1,5 @@
-use aoc_library::*;
-use day_4::scratch_card::*;
+use std::process::ExitCode;
 
-fn main() {
-    let input = read_file_to_vec("./2023/day_4/input.txt").unwrap();
-
-    print!("Total Winnings: {}", total_winnings(parse_input(input)));
+fn main() -> ExitCode {
+    aoc_library::run::main(&aoc_2023_day_4::DAY)
 }
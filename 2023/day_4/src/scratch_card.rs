@@ -1,100 +1,95 @@
+use aoc_library::checked::{self, Label, Overflow};
+use aoc_library::counter::Counter;
+use aoc_library::ints;
+#[cfg(not(feature = "parallel"))]
+use aoc_library::parse;
+use aoc_library::parse::{FromField, FromLine};
+use aoc_library::Error;
+
+#[derive(Debug, PartialEq, Eq, FromLine)]
+#[aoc(pattern = "Card {card_number}: {numbers} | {winning_numbers}")]
 pub struct ScratchCard {
     card_number: u32,
-    numbers: Vec<u32>,
-    winning_numbers: Vec<u32>,
+    numbers: Numbers,
+    winning_numbers: Numbers,
 }
 
 impl ScratchCard {
     pub fn new((card_number, numbers, winning_numbers): (u32, Vec<u32>, Vec<u32>)) -> ScratchCard {
         ScratchCard {
             card_number,
-            numbers,
-            winning_numbers,
+            numbers: Numbers(numbers.into_iter().collect()),
+            winning_numbers: Numbers(winning_numbers.into_iter().collect()),
         }
     }
 
-    pub fn point_total(&self) -> u32 {
-        let mut points = 0;
-
-        for number in &self.numbers {
-            if self.winning_numbers.contains(number) {
-                if points == 0 {
-                    points = 1;
-                } else {
-                    points <<= 1;
-                }
-            }
-        }
-
-        points
+    pub fn point_total(&self) -> u64 {
+        self.try_point_total()
+            .unwrap_or_else(|overflow| panic!("{overflow}"))
     }
-}
 
-pub fn parse_input(input: Vec<String>) -> Vec<ScratchCard> {
-    let mut cards: Vec<ScratchCard> = Vec::new();
-
-    for line in input {
-        let card = ScratchCard::new(parse_line(&line));
-        cards.push(card);
+    /// How many of the card's numbers are winning numbers. A number on
+    /// both sides more than once matches as often as the side with fewer
+    /// copies has it.
+    pub fn matches(&self) -> u64 {
+        self.numbers.0.intersection_len(&self.winning_numbers.0)
     }
 
-    cards
+    pub fn try_point_total(&self) -> Result<u64, Overflow> {
+        match self.matches() {
+            0 => Ok(0),
+            // One point for the first match, doubled for every other.
+            matches => u32::try_from(matches - 1)
+                .ok()
+                .and_then(|doublings| 1_u64.checked_shl(doublings))
+                .ok_or_else(|| Overflow::new(Label("card", self.card_number))),
+        }
+    }
 }
 
-pub fn total_winnings(cards: Vec<ScratchCard>) -> u32 {
-    let mut total = 0;
-
-    for card in cards {
-        total += card.point_total();
+/// The numbers on one side of a card, counted as they are read. Anything
+/// but whitespace between them is an error.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Numbers(Counter<u32>);
+
+impl FromField for Numbers {
+    fn from_field(text: &str, _separators: &[&str]) -> Result<Numbers, Error> {
+        ints::all(text)
+            .strict()
+            .collect::<Result<_, _>>()
+            .map(Numbers)
     }
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn parse_input(input: Vec<String>) -> Vec<ScratchCard> {
+    parse::lines(&input).unwrap_or_else(|error| panic!("{error}"))
+}
 
-    total
+/// Parses the cards in one chunk of lines per core.
+#[cfg(feature = "parallel")]
+pub fn parse_input(input: Vec<String>) -> Vec<ScratchCard> {
+    let numbered: Vec<(usize, &String)> = input.iter().enumerate().collect();
+    aoc_library::parallel::map_chunks(&numbered, |lines| {
+        lines
+            .iter()
+            .map(|&(index, line)| {
+                ScratchCard::from_line(line)
+                    .unwrap_or_else(|error| panic!("{}", error.on_line(index + 1)))
+            })
+            .collect::<Vec<_>>()
+    })
+    .into_iter()
+    .flatten()
+    .collect()
 }
 
-fn parse_line(line: &str) -> (u32, Vec<u32>, Vec<u32>) {
-    let split: Vec<_> = line.split(": ").collect();
-    let card_number = split
-        .first()
-        .unwrap()
-        .split(' ')
-        .last()
-        .unwrap()
-        .parse::<u32>()
-        .unwrap();
-
-    let numbers: Vec<u32> = split
-        .last()
-        .unwrap()
-        .split(" | ")
-        .collect::<Vec<_>>()
-        .first()
-        .unwrap()
-        .split(' ')
-        .collect::<Vec<_>>()
-        .iter()
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .iter()
-        .map(|num| num.parse::<u32>().unwrap())
-        .collect();
-
-    let winning_numbers: Vec<u32> = split
-        .last()
-        .unwrap()
-        .split(" | ")
-        .collect::<Vec<_>>()
-        .last()
-        .unwrap()
-        .split(' ')
-        .collect::<Vec<_>>()
-        .iter()
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .iter()
-        .map(|num| num.parse::<u32>().unwrap())
-        .collect();
-
-    (card_number, numbers, winning_numbers)
+pub fn total_winnings(cards: &[ScratchCard]) -> u64 {
+    checked::sum(
+        cards
+            .iter()
+            .map(|card| (Label("card", card.card_number), card.point_total())),
+    )
 }
 
 #[cfg(test)]
@@ -104,10 +99,25 @@ mod tests {
     #[test]
     fn test_parse_line() {
         let line = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let (card_number, numbers, winning_numbers) = parse_line(line);
-        assert_eq!(card_number, 1);
-        assert_eq!(numbers, vec![41, 48, 83, 86, 17]);
-        assert_eq!(winning_numbers, vec![83, 86, 6, 31, 17, 9, 48, 53]);
+        let card = ScratchCard::from_line(line).unwrap();
+        assert_eq!(card.card_number, 1);
+        let numbers = |numbers: &[u32]| Numbers(numbers.iter().copied().collect());
+        assert_eq!(card.numbers, numbers(&[41, 48, 83, 86, 17]));
+        assert_eq!(
+            card.winning_numbers,
+            numbers(&[83, 86, 6, 31, 17, 9, 48, 53])
+        );
+    }
+
+    #[test]
+    fn test_parse_line_reports_column() {
+        let error = ScratchCard::from_line("Card 1: 41 48 | 83 x6").unwrap_err();
+        assert_eq!(error.to_string(), "column 20: expected u32, found \"x6\"");
+        let error = ScratchCard::from_line("Card 1: 41 48").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "column 14: expected \" | \", found the end"
+        );
     }
 
     #[test]
@@ -119,4 +129,25 @@ mod tests {
         ));
         assert_eq!(card.point_total(), 8);
     }
+
+    #[test]
+    fn test_repeated_numbers_match_as_often_as_both_sides_have_them() {
+        let card = ScratchCard::new((2, vec![5, 5, 5, 7], vec![5, 5, 7, 7]));
+        assert_eq!(card.matches(), 3);
+        assert_eq!(card.point_total(), 4);
+    }
+
+    #[test]
+    fn test_point_total_beyond_32_matches() {
+        let numbers: Vec<u32> = (1..=40).collect();
+        let card = ScratchCard::new((3, numbers.clone(), numbers));
+        assert_eq!(card.point_total(), 1 << 39);
+    }
+
+    #[test]
+    fn test_point_total_reports_overflowing_card() {
+        let numbers: Vec<u32> = (1..=65).collect();
+        let card = ScratchCard::new((12, numbers.clone(), numbers));
+        assert_eq!(card.try_point_total().unwrap_err().item, "card 12");
+    }
 }
@@ -0,0 +1,111 @@
+use aoc_library::{checked, Day, Solution};
+use std::collections::HashMap;
+
+/// The historians' two location lists, column by column.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Lists {
+    pub left: Vec<u64>,
+    pub right: Vec<u64>,
+}
+
+pub fn parse_lists(lines: &[String]) -> Lists {
+    let mut lists = Lists::default();
+    for (index, line) in lines.iter().enumerate() {
+        let mut ids = line.split_whitespace().map(|id| {
+            id.parse::<u64>()
+                .unwrap_or_else(|error| panic!("line {}: {id:?}: {error}", index + 1))
+        });
+        match (ids.next(), ids.next(), ids.next()) {
+            (Some(left), Some(right), None) => {
+                lists.left.push(left);
+                lists.right.push(right);
+            }
+            (None, _, _) => continue,
+            _ => panic!("line {}: expected two location IDs", index + 1),
+        }
+    }
+    lists
+}
+
+/// Sum of the distances between the lists' smallest, second smallest, ...
+/// IDs.
+pub fn total_distance(lists: &Lists) -> u64 {
+    let mut left = lists.left.clone();
+    let mut right = lists.right.clone();
+    left.sort_unstable();
+    right.sort_unstable();
+    checked::sum(
+        (1..)
+            .zip(left.iter().zip(&right))
+            .map(|(pair, (a, b))| (checked::Label("pair", pair), a.abs_diff(*b))),
+    )
+}
+
+/// Sum of every left ID times the number of times it appears on the right.
+pub fn similarity_score(lists: &Lists) -> u64 {
+    let mut counts: HashMap<u64, u64> = HashMap::new();
+    for &id in &lists.right {
+        *counts.entry(id).or_default() += 1;
+    }
+    checked::sum(lists.left.iter().map(|&id| {
+        let score = id
+            .checked_mul(counts.get(&id).copied().unwrap_or(0))
+            .unwrap_or_else(|| panic!("u64 overflow at score of {id}"));
+        (checked::Label("id", id), score)
+    }))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+
+    type Input = Lists;
+
+    fn parse(lines: Vec<String>) -> Lists {
+        parse_lists(&lines)
+    }
+
+    // No input has been added yet, and empty lists have no answer.
+    fn part_one(lists: &Lists) -> Option<u64> {
+        (!lists.left.is_empty()).then(|| total_distance(lists))
+    }
+
+    fn part_two(lists: &Lists) -> Option<u64> {
+        (!lists.left.is_empty()).then(|| similarity_score(lists))
+    }
+}
+
+pub const DAY: Day = Day::new::<Day1>(env!("CARGO_MANIFEST_DIR"));
+
+aoc_library::aoc_examples!(DAY);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_lists() {
+        let lists = parse_lists(&lines(&["3   4", "", "10 2"]));
+        assert_eq!(lists.left, [3, 10]);
+        assert_eq!(lists.right, [4, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2: expected two location IDs")]
+    fn test_parse_rejects_single_column() {
+        parse_lists(&lines(&["1 2", "3"]));
+    }
+
+    #[test]
+    fn test_distance_and_similarity() {
+        let lists = parse_lists(&lines(&["5 1", "1 5", "2 5"]));
+        assert_eq!(total_distance(&lists), 3);
+        assert_eq!(similarity_score(&lists), 11);
+    }
+}
@@ -0,0 +1,5 @@
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    aoc_library::run::main(&aoc_2024_day_1::DAY)
+}
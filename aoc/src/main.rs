@@ -0,0 +1,934 @@
+//! Runs, times and benchmarks the registered days of every year.
+//!
+//! ```text
+//! aoc <year> <day> [--json] [--quiet|--verbose] [--timeout SECS] [--force] [--param NAME=VALUE]...
+//! aoc <year> [--json] [--quiet|--verbose] [--jobs N] [--timeout SECS] [--force]
+//! aoc all [--json] [--quiet|--verbose] [--jobs N] [--timeout SECS] [--force]
+//! aoc verify
+//! aoc new <year> <day>
+//! aoc fetch <year> <day> [--refresh]
+//! aoc watch <year> <day>
+//! aoc report [--html]
+//! aoc bench [<year> <day>] [--warmup N] [--iterations N] [--compare] [--threshold PERCENT]
+//! ```
+//!
+//! `<year>` runs every registered day of that year, `all` every day of
+//! every year, several days at a time on `--jobs` threads (one per core by
+//! default) with results printed in day order. With `--json` a single day
+//! prints one JSON object and `<year>` and `all` print the days plus a
+//! summary; see `aoc_library::run` for the fields. `--quiet` prints only
+//! the answers and `--verbose` adds each day's input and parameters. A day
+//! that cannot be run, for a missing input, bad settings or a panic, is
+//! reported as ERROR in its place; the other days still run and `aoc`
+//! exits non-zero.
+//!
+//! Settings come from `aoc.toml` in the workspace root and in each day's
+//! crate: where the session token, caches and inputs live, the default
+//! verbosity and each day's puzzle parameters; see `aoc_library::config`.
+//! `--param` overrides one of a single day's parameters for this run, e.g.
+//! `aoc 2023 2 --param red=20`; see `aoc_library::params`.
+//!
+//! Each stage may run for `--timeout` seconds (60 by default, 0 for no
+//! limit). A stage that overruns is cancelled and reported as TIMEOUT, and
+//! the run goes on with the next day; see `aoc_library::cancel` for how
+//! solutions notice.
+//!
+//! Results are cached in the `results` directory of the cache, keyed by a
+//! hash of the day's crate, input and parameters, `aoc_library`,
+//! `aoc_macros` and the build's profile and features, and a day none of
+//! them changed for is served from the cache. `--force` solves every day
+//! again, as does a build with `alloc-stats`, which needs fresh memory
+//! figures.
+//!
+//! `verify` checks every day against the answers recorded in its
+//! `answers.toml` and exits non-zero on any mismatch.
+//!
+//! `new` generates `<year>/day_<day>` from aoc_library's template and
+//! registers it in this crate's `Cargo.toml` and `registry.rs`.
+//!
+//! `fetch` prints the puzzle description as markdown and adds its example
+//! blocks to the day's `examples` directory. Pages are cached in the cache
+//! directory; `--refresh` fetches again, e.g. once part two is unlocked,
+//! which needs the session cookie from `AOC_SESSION` or the session file.
+//!
+//! `watch` runs a day, then polls its crate, `aoc_library`, `aoc_macros`
+//! and the runner for changes. A changed input is re-run in place; a
+//! changed source is rebuilt with cargo and the new binary takes over,
+//! carrying the previous answers along so each run shows which answers
+//! changed.
+//!
+//! `report` prints a markdown progress table of every registered day, or
+//! with `--html` a self-contained page: stars from each `answers.toml`,
+//! runtimes from the benchmark history and links to the sources. Links
+//! are relative to the workspace root, so save it there.
+//!
+//! Building with `--features alloc-stats` adds allocation counts, bytes
+//! allocated and peak heap per stage to the output of a run. The counters
+//! are process-wide, so they are only reported when days run one at a
+//! time: for a single day or with `--jobs 1`.
+//!
+//! Every benchmark is appended to `.aoc/bench_history.tsv` in the workspace
+//! root; `--compare` flags stages slower than the previous run on this
+//! machine by more than the threshold (10% by default).
+
+mod registry;
+
+use aoc_library::bench::{bench_day, BenchConfig};
+use aoc_library::cache::{self, ResultCache};
+use aoc_library::config::{Config, DayConfig, Verbosity, CONFIG_FILE};
+use aoc_library::history::{self, Record};
+use aoc_library::html;
+use aoc_library::http::Curl;
+use aoc_library::memory;
+use aoc_library::parallel;
+use aoc_library::params::{self, Params};
+use aoc_library::puzzle::{self, Fetcher};
+use aoc_library::report::Report;
+use aoc_library::run::{self, DayError, DayRun};
+use aoc_library::scaffold::{self, NewDay};
+use aoc_library::solution::{Answers, NoMeter, Stage};
+use aoc_library::verify::{self, Verdict};
+use aoc_library::watch::{self, Snapshot};
+use aoc_library::{read_file_to_vec, Day};
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run {
+        year: u16,
+        day: u8,
+        options: RunOptions,
+    },
+    Year {
+        year: u16,
+        options: RunOptions,
+    },
+    All(RunOptions),
+    Verify,
+    New {
+        year: u16,
+        day: u8,
+    },
+    Fetch {
+        year: u16,
+        day: u8,
+        refresh: bool,
+    },
+    Watch {
+        year: u16,
+        day: u8,
+        /// Answers of the run before a restart, passed as `--previous`.
+        previous: Option<Answers>,
+    },
+    Report {
+        html: bool,
+    },
+    Bench {
+        /// `None` benchmarks every registered day.
+        target: Option<(u16, u8)>,
+        options: BenchOptions,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+struct RunOptions {
+    json: bool,
+    /// Days solved at once; `None` uses one thread per core.
+    jobs: Option<usize>,
+    /// Time limit per stage; `None` lets every stage finish.
+    timeout: Option<Duration>,
+    /// Solve again even when the cached results are up to date.
+    force: bool,
+    /// Overrides the configured verbosity.
+    verbosity: Option<Verbosity>,
+    /// Overrides the configured parameters of a single day.
+    params: Params,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            json: false,
+            jobs: None,
+            timeout: Some(DEFAULT_TIMEOUT),
+            force: false,
+            verbosity: None,
+            params: Params::new(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct BenchOptions {
+    config: BenchConfig,
+    compare: bool,
+    threshold: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            config: BenchConfig::default(),
+            compare: false,
+            threshold: 10.0,
+        }
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: aoc_library::memory::CountingAllocator = aoc_library::memory::CountingAllocator;
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+fn history_path() -> PathBuf {
+    workspace_root().join(".aoc").join("bench_history.tsv")
+}
+
+fn config() -> Result<Config, String> {
+    Config::load(workspace_root())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{message}");
+            eprintln!("usage: aoc <year> <day> [--json] [--quiet|--verbose] [--timeout SECS] [--force] [--param NAME=VALUE]... | aoc <year> [--json] [--quiet|--verbose] [--jobs N] [--timeout SECS] [--force] | aoc all [--json] [--quiet|--verbose] [--jobs N] [--timeout SECS] [--force] | aoc verify | aoc new <year> <day> | aoc fetch <year> <day> [--refresh] | aoc watch <year> <day> | aoc report [--html] | aoc bench [<year> <day>] [--warmup N] [--iterations N] [--compare] [--threshold PERCENT]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::Run { year, day, options } => run_days(&[lookup(year, day)?], &options),
+        Command::Year { year, options } => {
+            let days = registry::year(year);
+            if days.is_empty() {
+                return Err(format!("no days of {year} are registered"));
+            }
+            run_days(&days, &options)
+        }
+        Command::All(options) => run_days(&registry::days(), &options),
+        Command::Verify => verify_all(&config()?),
+        Command::New { year, day } => new_day(year, day),
+        Command::Fetch { year, day, refresh } => fetch(&config()?, year, day, refresh),
+        Command::Watch {
+            year,
+            day,
+            previous,
+        } => watch(&config()?, lookup(year, day)?, previous),
+        Command::Report { html } => report(html),
+        Command::Bench { target, options } => {
+            let days = match target {
+                Some((year, day)) => vec![lookup(year, day)?],
+                None => registry::days(),
+            };
+            bench(&config()?, &days, &options)
+        }
+    }
+}
+
+fn lookup(year: u16, day: u8) -> Result<&'static Day, String> {
+    registry::find(year, day).ok_or_else(|| format!("{year} day {day} is not registered"))
+}
+
+fn read_input(settings: &DayConfig) -> Result<Vec<String>, String> {
+    read_file_to_vec(&settings.input.to_string_lossy())
+        .map_err(|error| format!("{}: {error}", settings.input.display()))
+}
+
+/// Solves `day`, or serves its results from the cache when nothing they
+/// depend on has changed.
+fn run_day(
+    config: &Config,
+    day: &Day,
+    settings: &DayConfig,
+    options: &RunOptions,
+) -> Result<DayRun, String> {
+    let cache = ResultCache {
+        dir: config.cache_dir.join("results"),
+    };
+    let key = cache::fingerprint(day, settings, &build_args().join(" "))?;
+    if !options.force && !memory::is_active() {
+        if let Some(run) = cache.load(day, key)? {
+            return Ok(run);
+        }
+    }
+    let run = run::run(
+        day,
+        &read_input(settings)?,
+        &settings.params,
+        options.timeout,
+    )?;
+    if let Err(error) = cache.store(&run, key) {
+        eprintln!("{day}: not cached: {error}");
+    }
+    Ok(run)
+}
+
+/// Solves `days` on a pool of threads and prints them in order, or with
+/// `json` all of them and, for more than one day, a summary. A day that
+/// cannot be run is reported in its place and fails the command once every
+/// other day has run.
+fn run_days(days: &[&Day], options: &RunOptions) -> Result<(), String> {
+    let config = config()?;
+    let verbosity = options.verbosity.unwrap_or(config.verbosity);
+    let jobs = options
+        .jobs
+        .unwrap_or_else(parallel::available_jobs)
+        .min(days.len().max(1));
+    let results = parallel::map(days, jobs, |day| {
+        let mut settings = config.day(day)?;
+        params::apply(day.params, &mut settings.params, &options.params)?;
+        let run = run_day(&config, day, &settings, options)?;
+        Ok::<_, String>((run, settings))
+    });
+
+    if jobs > 1 && memory::is_active() {
+        eprintln!("memory usage is not reported for days run concurrently; use --jobs 1");
+    }
+    let mut runs = Vec::new();
+    let mut errors = Vec::new();
+    for (day, result) in days.iter().zip(results) {
+        let (mut run, settings) = match result {
+            Ok(solved) => solved,
+            Err(message) => {
+                let error = DayError::new(day, message);
+                match (options.json, verbosity) {
+                    (true, _) => {}
+                    (false, Verbosity::Quiet) => println!("{}", error.brief()),
+                    (false, _) => print!("{error}"),
+                }
+                errors.push(error);
+                continue;
+            }
+        };
+        if jobs > 1 {
+            // Allocation counters are process-wide, so concurrent days
+            // would be charged for each other's allocations.
+            run.memory = None;
+        }
+        if !options.json {
+            print_run(&run, &settings, verbosity);
+        }
+        runs.push(run);
+    }
+    match (options.json, runs.as_slice(), errors.as_slice()) {
+        (false, _, _) => {}
+        (true, [run], []) if days.len() == 1 => println!("{}", run.to_json()),
+        (true, [], [error]) if days.len() == 1 => println!("{}", error.to_json()),
+        (true, runs, errors) => println!("{}", run::summary_json(runs, errors)),
+    }
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(format!(
+            "{} day {} could not be run",
+            errors[0].year, errors[0].day
+        )),
+        count => Err(format!("{count} days could not be run")),
+    }
+}
+
+fn print_run(run: &DayRun, settings: &DayConfig, verbosity: Verbosity) {
+    match verbosity {
+        Verbosity::Quiet => println!("{}", run.brief()),
+        Verbosity::Normal => print!("{run}"),
+        Verbosity::Verbose => {
+            print!("{run}");
+            println!("  input     {}", settings.input.display());
+            if !settings.params.is_empty() {
+                let params: Vec<String> = settings
+                    .params
+                    .iter()
+                    .map(|(name, value)| format!("{name}={value}"))
+                    .collect();
+                println!("  params    {}", params.join(" "));
+            }
+        }
+    }
+}
+
+fn new_day(year: u16, day: u8) -> Result<(), String> {
+    let new_day = NewDay::new(year, day);
+    let dir = scaffold::create(
+        workspace_root(),
+        Path::new(env!("CARGO_MANIFEST_DIR")),
+        &new_day,
+    )?;
+    println!("Created {} and registered {year} day {day}", dir.display());
+    println!(
+        "Add the puzzle input to {}",
+        dir.join("input.txt").display()
+    );
+    Ok(())
+}
+
+fn fetch(config: &Config, year: u16, day: u8, refresh: bool) -> Result<(), String> {
+    let fetcher = Fetcher {
+        backend: &Curl,
+        cache_dir: config.cache_dir.clone(),
+        session: puzzle::session_token(&config.session_file),
+    };
+    let page = fetcher.puzzle_html(year, day, refresh)?;
+    print!("{}", html::to_markdown(&page));
+
+    let crate_dir = match registry::find(year, day) {
+        Some(day) => PathBuf::from(day.dir),
+        None => workspace_root().join(NewDay::new(year, day).relative_dir()),
+    };
+    if !crate_dir.exists() {
+        println!("\nRun `aoc new {year} {day}` first to keep the examples.");
+        return Ok(());
+    }
+    let written = puzzle::write_examples(&crate_dir.join("examples"), &page)?;
+    if !written.is_empty() {
+        println!();
+    }
+    for path in written {
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn watch(config: &Config, day: &'static Day, mut previous: Option<Answers>) -> Result<(), String> {
+    // Taken before any rebuild replaces the binary.
+    let exe = std::env::current_exe().map_err(|error| format!("locating aoc: {error}"))?;
+    let settings = config.day(day)?;
+    let input = settings.input.clone();
+    let mut roots = vec![
+        PathBuf::from(day.dir),
+        input.clone(),
+        workspace_root().join(CONFIG_FILE),
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+    ];
+    roots.extend(cache::library_dirs());
+    let mut snapshot = Snapshot::take(&roots);
+    println!("Watching {} (Ctrl-C to stop)\n", day.dir);
+
+    loop {
+        previous = watch_run(day, &settings, previous);
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let current = Snapshot::take(&roots);
+            let changes = current.changes_since(&snapshot);
+            if changes.is_empty() {
+                continue;
+            }
+            snapshot = current;
+            println!();
+            for path in &changes {
+                println!("changed {}", path.display());
+            }
+            if changes.iter().all(|path| *path == input) {
+                break;
+            }
+            if rebuild() {
+                return restart(&exe, day, previous);
+            }
+            println!("build failed, waiting for changes");
+        }
+    }
+}
+
+/// Solves `day` and prints the answers and their changes since `previous`.
+/// A missing input or a panic keeps the previous answers.
+fn watch_run(day: &Day, settings: &DayConfig, previous: Option<Answers>) -> Option<Answers> {
+    let solved =
+        read_input(settings).and_then(|lines| run::run(day, &lines, &settings.params, None));
+    let run = match solved {
+        Ok(run) => run,
+        Err(message) => {
+            println!("{message}");
+            return previous;
+        }
+    };
+
+    print!("{run}");
+    let answers = run.answers;
+    if let Some(previous) = previous {
+        let changes = watch::diff(&previous, &answers);
+        if changes.is_empty() {
+            println!("  answers unchanged");
+        }
+        for change in changes {
+            let show = |answer: Option<u64>| answer.map_or("-".to_string(), |a| a.to_string());
+            println!(
+                "  {} changed: {} -> {}",
+                change.stage,
+                show(change.before),
+                show(change.after)
+            );
+        }
+    }
+    Some(answers)
+}
+
+/// The cargo arguments for the profile and features of this binary.
+fn build_args() -> Vec<&'static str> {
+    let mut args = Vec::new();
+    if !cfg!(debug_assertions) {
+        args.push("--release");
+    }
+    if cfg!(feature = "alloc-stats") {
+        args.extend(["--features", "alloc-stats"]);
+    }
+    if cfg!(feature = "parallel") {
+        args.extend(["--features", "parallel"]);
+    }
+    args
+}
+
+/// Rebuilds the runner with the profile and features of this binary.
+fn rebuild() -> bool {
+    process::Command::new("cargo")
+        .args(["build", "--quiet", "--package", "aoc"])
+        .args(build_args())
+        .current_dir(workspace_root())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Hands over to the rebuilt binary, which carries on watching.
+fn restart(exe: &Path, day: &Day, previous: Option<Answers>) -> Result<(), String> {
+    let mut command = process::Command::new(exe);
+    command.args(["watch", &day.year.to_string(), &day.day.to_string()]);
+    if let Some(previous) = &previous {
+        command.args(["--previous", &watch::encode_answers(previous)]);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(format!("restarting {}: {}", exe.display(), command.exec()))
+    }
+    #[cfg(not(unix))]
+    {
+        let status = command
+            .status()
+            .map_err(|error| format!("restarting {}: {error}", exe.display()))?;
+        process::exit(status.code().unwrap_or(1))
+    }
+}
+
+fn report(html: bool) -> Result<(), String> {
+    let path = history_path();
+    let history = history::load(&path).map_err(|error| format!("{}: {error}", path.display()))?;
+    let report = Report::build(
+        &registry::days(),
+        workspace_root(),
+        &history,
+        &history::machine(),
+    )?;
+    match html {
+        true => print!("{}", report.html()),
+        false => print!("{}", report.markdown()),
+    }
+    Ok(())
+}
+
+fn verify_all(config: &Config) -> Result<(), String> {
+    println!("{:<12}{:<32}{:<32}", "day", "part one", "part two");
+    let mut failures = 0;
+
+    for day in registry::days() {
+        let label = format!("{} {}", day.year, day.day);
+        match verify_day(config, day) {
+            Ok(verdicts) => {
+                failures += verdicts.iter().filter(|v| v.is_failure()).count();
+                println!("{label:<12}{:<32}{:<32}", verdicts[0], verdicts[1]);
+            }
+            Err(message) => {
+                failures += 1;
+                println!("{label:<12}ERROR {message}");
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{failures} verification failure(s)"));
+    }
+    Ok(())
+}
+
+fn verify_day(config: &Config, day: &Day) -> Result<[Verdict; 2], String> {
+    let expected = verify::expected_answers(day)?.unwrap_or_default();
+    let settings = config.day(day)?;
+    let lines = read_input(&settings)?;
+    let answers = std::panic::catch_unwind(|| {
+        params::with_params(&settings.params, || day.solve(&lines, &mut NoMeter))
+    })
+    .map_err(|_| "solution panicked".to_string())?;
+    Ok(verify::verify(&expected, &answers))
+}
+
+fn bench(config: &Config, days: &[&Day], options: &BenchOptions) -> Result<(), String> {
+    let path = history_path();
+    let previous = history::load(&path).map_err(|error| format!("{}: {error}", path.display()))?;
+    let commit = history::git_commit(workspace_root());
+    let machine = history::machine();
+    let mut records = Vec::new();
+
+    for day in days {
+        let settings = config.day(day)?;
+        let lines = read_input(&settings)?;
+        let report =
+            params::with_params(&settings.params, || bench_day(day, &lines, options.config));
+        print_bench(day, &options.config, &report);
+        records.extend(Record::from_report(day, &report, &commit, &machine));
+    }
+
+    history::append(&path, &records).map_err(|error| format!("{}: {error}", path.display()))?;
+
+    if options.compare {
+        let regressions = history::compare(&previous, &records, options.threshold);
+        if regressions.is_empty() {
+            println!("No regressions above {}% on {machine}", options.threshold);
+        }
+        for regression in &regressions {
+            println!(
+                "REGRESSION {} day {} {}: {:.2?} -> {:.2?} (+{:.1}% since {})",
+                regression.year,
+                regression.day,
+                regression.stage,
+                regression.before,
+                regression.after,
+                regression.slowdown,
+                regression.baseline_commit
+            );
+        }
+        if !regressions.is_empty() {
+            return Err(format!("{} stage(s) regressed", regressions.len()));
+        }
+    }
+    Ok(())
+}
+
+fn print_bench(day: &Day, config: &BenchConfig, report: &aoc_library::bench::BenchReport) {
+    println!(
+        "{day} ({} warm-up, {} timed iterations)",
+        config.warmup, config.iterations
+    );
+    println!(
+        "  {:<10}{:>12}{:>12}{:>12}{:>12}",
+        "stage", "mean", "median", "min", "stddev"
+    );
+    for stage in Stage::ALL {
+        let stats = report.get(stage);
+        println!(
+            "  {stage:<10}{:>12.2?}{:>12.2?}{:>12.2?}{:>12.2?}",
+            stats.mean, stats.median, stats.min, stats.stddev
+        );
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["all", flags @ ..] => Ok(Command::All(parse_days_options(flags)?)),
+        ["verify"] => Ok(Command::Verify),
+        ["new", year, day] => Ok(Command::New {
+            year: parse_number(year)?,
+            day: parse_number(day)?,
+        }),
+        ["fetch", year, day, options @ ..] => Ok(Command::Fetch {
+            year: parse_number(year)?,
+            day: parse_number(day)?,
+            refresh: match options {
+                [] => false,
+                ["--refresh"] => true,
+                _ => return Err("unrecognised arguments".to_string()),
+            },
+        }),
+        ["watch", year, day, options @ ..] => Ok(Command::Watch {
+            year: parse_number(year)?,
+            day: parse_number(day)?,
+            previous: match options {
+                [] => None,
+                ["--previous", answers] => Some(
+                    watch::decode_answers(answers)
+                        .ok_or_else(|| format!("invalid previous answers {answers:?}"))?,
+                ),
+                _ => return Err("unrecognised arguments".to_string()),
+            },
+        }),
+        ["report"] => Ok(Command::Report { html: false }),
+        ["report", "--html"] => Ok(Command::Report { html: true }),
+        ["bench", rest @ ..] => {
+            let (target, options) = match rest {
+                [year, day, options @ ..] if !year.starts_with("--") => {
+                    (Some((parse_number(year)?, parse_number(day)?)), options)
+                }
+                options => (None, options),
+            };
+            Ok(Command::Bench {
+                target,
+                options: parse_bench_options(options)?,
+            })
+        }
+        [year, flags @ ..] if flags.first().is_none_or(|flag| flag.starts_with("--")) => {
+            Ok(Command::Year {
+                year: parse_number(year)?,
+                options: parse_days_options(flags)?,
+            })
+        }
+        [year, day, flags @ ..] => {
+            let options = parse_run_options(flags)?;
+            if options.jobs.is_some() {
+                return Err("--jobs applies to several days".to_string());
+            }
+            Ok(Command::Run {
+                year: parse_number(year)?,
+                day: parse_number(day)?,
+                options,
+            })
+        }
+        _ => Err("unrecognised arguments".to_string()),
+    }
+}
+
+fn parse_run_options(args: &[&str]) -> Result<RunOptions, String> {
+    let mut options = RunOptions::default();
+    let mut args = args.iter();
+    while let Some(option) = args.next() {
+        match *option {
+            "--json" => options.json = true,
+            "--force" => options.force = true,
+            "--quiet" => options.verbosity = Some(Verbosity::Quiet),
+            "--verbose" => options.verbosity = Some(Verbosity::Verbose),
+            "--param" => {
+                let assignment = args.next().ok_or("--param needs name=value")?;
+                let (name, value) = params::parse_assignment(assignment)?;
+                options.params.insert(name, value);
+            }
+            "--jobs" => {
+                let jobs = args.next().ok_or("--jobs needs a value")?;
+                match parse_number(jobs)? {
+                    0 => return Err("--jobs must be at least 1".to_string()),
+                    jobs => options.jobs = Some(jobs),
+                }
+            }
+            "--timeout" => {
+                let seconds = args.next().ok_or("--timeout needs a value")?;
+                let seconds: f64 = parse_number(seconds)?;
+                options.timeout = match seconds {
+                    0.0 => None,
+                    seconds => Some(
+                        Duration::try_from_secs_f64(seconds)
+                            .map_err(|_| format!("invalid timeout {seconds}"))?,
+                    ),
+                };
+            }
+            _ => return Err(format!("unknown option {option}")),
+        }
+    }
+    Ok(options)
+}
+
+/// Options for running several days, where parameters do not apply.
+fn parse_days_options(args: &[&str]) -> Result<RunOptions, String> {
+    let options = parse_run_options(args)?;
+    if !options.params.is_empty() {
+        return Err("--param applies to a single day".to_string());
+    }
+    Ok(options)
+}
+
+fn parse_bench_options(args: &[&str]) -> Result<BenchOptions, String> {
+    let mut options = BenchOptions::default();
+    let mut args = args.iter();
+    while let Some(option) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{option} needs a value"));
+        match *option {
+            "--warmup" => options.config.warmup = parse_number(value()?)?,
+            "--iterations" => match parse_number(value()?)? {
+                0 => return Err("--iterations must be at least 1".to_string()),
+                iterations => options.config.iterations = iterations,
+            },
+            "--threshold" => options.threshold = parse_number(value()?)?,
+            "--compare" => options.compare = true,
+            _ => return Err(format!("unknown option {option}")),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{value:?} is not a valid number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_parse_run_and_all() {
+        assert_eq!(
+            parse_args(&args("2023 3")),
+            Ok(Command::Run {
+                year: 2023,
+                day: 3,
+                options: RunOptions::default()
+            })
+        );
+        assert_eq!(
+            parse_args(&args("2023 3 --json --timeout 0 --force --param red=20")),
+            Ok(Command::Run {
+                year: 2023,
+                day: 3,
+                options: RunOptions {
+                    json: true,
+                    jobs: None,
+                    timeout: None,
+                    force: true,
+                    verbosity: None,
+                    params: Params::from([("red".to_string(), 20)])
+                }
+            })
+        );
+        assert!(parse_args(&args("2023 3 --param red")).is_err());
+        assert!(parse_args(&args("2023 --param red=20")).is_err());
+        assert!(parse_args(&args("2023 3 --jobs 2")).is_err());
+        assert_eq!(
+            parse_args(&args("2024")),
+            Ok(Command::Year {
+                year: 2024,
+                options: RunOptions::default()
+            })
+        );
+        assert_eq!(
+            parse_args(&args("2024 --json")),
+            Ok(Command::Year {
+                year: 2024,
+                options: RunOptions {
+                    json: true,
+                    ..RunOptions::default()
+                }
+            })
+        );
+        assert_eq!(
+            parse_args(&args("all")),
+            Ok(Command::All(RunOptions::default()))
+        );
+        assert_eq!(
+            parse_args(&args("all --jobs 3 --json --timeout 1.5 --quiet")),
+            Ok(Command::All(RunOptions {
+                json: true,
+                jobs: Some(3),
+                timeout: Some(Duration::from_millis(1500)),
+                force: false,
+                verbosity: Some(Verbosity::Quiet),
+                params: Params::new()
+            }))
+        );
+        assert!(parse_args(&args("all --timeout -1")).is_err());
+        assert!(parse_args(&args("all --yaml")).is_err());
+        assert!(parse_args(&args("all --jobs 0")).is_err());
+        assert!(parse_args(&args("all --jobs")).is_err());
+        assert_eq!(
+            parse_args(&args("report --html")),
+            Ok(Command::Report { html: true })
+        );
+        assert_eq!(parse_args(&args("verify")), Ok(Command::Verify));
+        assert_eq!(
+            parse_args(&args("new 2023 5")),
+            Ok(Command::New { year: 2023, day: 5 })
+        );
+        assert_eq!(
+            parse_args(&args("fetch 2023 4 --refresh")),
+            Ok(Command::Fetch {
+                year: 2023,
+                day: 4,
+                refresh: true
+            })
+        );
+        assert!(parse_args(&args("fetch 2023 4 --force")).is_err());
+        assert_eq!(
+            parse_args(&args("watch 2023 3 --previous 4361,-")),
+            Ok(Command::Watch {
+                year: 2023,
+                day: 3,
+                previous: Some(Answers {
+                    part_one: Some(4361),
+                    part_two: None
+                })
+            })
+        );
+        assert!(parse_args(&args("watch 2023 3 --previous 1")).is_err());
+        assert!(parse_args(&args("2023 three")).is_err());
+        assert!(parse_args(&args("")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_options() {
+        let Ok(Command::Bench { target, options }) =
+            parse_args(&args("bench 2023 4 --iterations 7 --warmup 1"))
+        else {
+            panic!("expected a bench command");
+        };
+        assert_eq!(target, Some((2023, 4)));
+        assert_eq!((options.config.warmup, options.config.iterations), (1, 7));
+        assert!(!options.compare);
+
+        assert!(parse_args(&args("bench 2023 4 --iterations")).is_err());
+        assert!(parse_args(&args("bench 2023 4 --iterations 0")).is_err());
+        assert!(parse_args(&args("bench 2023 4 --fast 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench_compare_without_day() {
+        let Ok(Command::Bench { target, options }) =
+            parse_args(&args("bench --compare --threshold 25"))
+        else {
+            panic!("expected a bench command");
+        };
+        assert_eq!(target, None);
+        assert!(options.compare);
+        assert_eq!(options.threshold, 25.0);
+    }
+
+    #[test]
+    fn test_registered_days_match_recorded_answers() {
+        for day in registry::days() {
+            let verdicts = verify_day(&config().unwrap(), day).unwrap();
+            assert!(
+                !verdicts.iter().any(Verdict::is_failure),
+                "{day}: {verdicts:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_registry_days_are_unique() {
+        let mut keys: Vec<(u16, u8)> = registry::DAYS.iter().map(|d| (d.year, d.day)).collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), registry::DAYS.len());
+        assert!(registry::find(2023, 3).is_some());
+        assert_eq!(registry::year(2024).len(), 1);
+        assert!(registry::find(2023, 25).is_none());
+    }
+}
@@ -0,0 +1,28 @@
+use aoc_library::Day;
+
+/// Every day the runner knows about, across all years. `aoc new` appends
+/// to this list.
+pub const DAYS: &[Day] = &[
+    aoc_2023_day_1::DAY,
+    aoc_2023_day_2::DAY,
+    aoc_2023_day_3::DAY,
+    aoc_2023_day_4::DAY,
+    aoc_2024_day_1::DAY,
+];
+
+/// Registered days in calendar order.
+pub fn days() -> Vec<&'static Day> {
+    let mut days: Vec<&Day> = DAYS.iter().collect();
+    days.sort_by_key(|day| (day.year, day.day));
+    days
+}
+
+/// Registered days of one year, in order.
+pub fn year(year: u16) -> Vec<&'static Day> {
+    days().into_iter().filter(|day| day.year == year).collect()
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter()
+        .find(|entry| entry.year == year && entry.day == day)
+}
@@ -0,0 +1,156 @@
+//! Repeated timing of each stage of a [`Day`].
+
+use crate::solution::{Day, Meter, Stage};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Untimed runs of each stage before measuring.
+    pub warmup: u32,
+    pub iterations: u32,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 10,
+            iterations: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Returns `None` when there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len() as f64;
+
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Some(Stats {
+            mean: Duration::from_secs_f64(mean),
+            median,
+            min: sorted[0],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub config: BenchConfig,
+    pub parse: Stats,
+    pub part_one: Stats,
+    pub part_two: Stats,
+}
+
+impl BenchReport {
+    pub fn get(&self, stage: Stage) -> Stats {
+        match stage {
+            Stage::Parse => self.parse,
+            Stage::PartOne => self.part_one,
+            Stage::PartTwo => self.part_two,
+        }
+    }
+}
+
+/// Runs each stage `warmup` times untimed, then `iterations` times timed.
+struct BenchMeter {
+    config: BenchConfig,
+    samples: [Vec<Duration>; 3],
+}
+
+impl Meter for BenchMeter {
+    fn measure(&mut self, stage: Stage, run: &mut dyn FnMut()) {
+        for _ in 0..self.config.warmup {
+            run();
+        }
+
+        let samples = &mut self.samples[stage as usize];
+        for _ in 0..self.config.iterations.max(1) {
+            let start = Instant::now();
+            run();
+            samples.push(start.elapsed());
+        }
+    }
+}
+
+pub fn bench_day(day: &Day, lines: &[String], config: BenchConfig) -> BenchReport {
+    let mut meter = BenchMeter {
+        config,
+        samples: Default::default(),
+    };
+    day.solve(lines, &mut meter);
+
+    let stats = |stage: Stage| Stats::from_samples(&meter.samples[stage as usize]).unwrap();
+    BenchReport {
+        config,
+        parse: stats(Stage::Parse),
+        part_one: stats(Stage::PartOne),
+        part_two: stats(Stage::PartTwo),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::tests::{fixture_lines, FIXTURE};
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(&[ms(4), ms(2), ms(6), ms(8)]).unwrap();
+        assert_eq!(stats.mean, ms(5));
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.stddev.as_micros(), 2236);
+
+        let stats = Stats::from_samples(&[ms(3), ms(1), ms(2)]).unwrap();
+        assert_eq!(stats.median, ms(2));
+        assert_eq!(stats.stddev.as_micros(), 816);
+
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_bench_day_runs_every_stage() {
+        let config = BenchConfig {
+            warmup: 2,
+            iterations: 5,
+        };
+        let report = bench_day(&FIXTURE, &fixture_lines(), config);
+        for stage in Stage::ALL {
+            let stats = report.get(stage);
+            assert!(stats.min <= stats.median);
+            assert!(stats.min <= stats.mean);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Answers of earlier runs, so unchanged days need not be solved again.
+//!
+//! A day's results are keyed by a hash of its input, its parameters, the
+//! build profile and features, and every file in its crate, in
+//! `aoc_library` and in `aoc_macros`, skipping build output and hidden
+//! files as watch mode does. Changing any of them changes the key and the
+//! day is solved afresh. Each day has one entry:
+//!
+//! ```text
+//! <cache>/2023/day_4.toml
+//!     key = "9f0c2b6a41d7e358"
+//!     part_one = 24848
+//!     parse_ns = 2630000
+//!     part_one_ns = 589790
+//!     part_two_ns = 102000
+//! ```
+
+use crate::config::DayConfig;
+use crate::run::DayRun;
+use crate::solution::{Answers, Day, Stage, Timings};
+use crate::toml::{self, Document, Value};
+use crate::verify;
+use crate::watch::Snapshot;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The directories of `aoc_library` and of `aoc_macros`, which its derives
+/// expand from: sources every day depends on.
+pub fn library_dirs() -> [PathBuf; 2] {
+    let library = Path::new(env!("CARGO_MANIFEST_DIR"));
+    [library.to_path_buf(), library.with_file_name("aoc_macros")]
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a, which unlike `DefaultHasher` is the same in every build.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Hashes the names and contents of every file below `roots`.
+pub fn hash_files(roots: &[PathBuf]) -> Result<u64, String> {
+    let mut hash = FNV_OFFSET;
+    for path in Snapshot::take(roots).files() {
+        let contents = fs::read(path).map_err(|error| format!("{}: {error}", path.display()))?;
+        hash = fnv1a(hash, path.to_string_lossy().as_bytes());
+        hash = fnv1a(hash, &(contents.len() as u64).to_le_bytes());
+        hash = fnv1a(hash, &contents);
+    }
+    Ok(hash)
+}
+
+/// The key for `day` with `settings`: its crate, input and parameters, the
+/// library, and `build`, which names the profile and features the runner
+/// was built with.
+pub fn fingerprint(day: &Day, settings: &DayConfig, build: &str) -> Result<u64, String> {
+    let mut roots = vec![PathBuf::from(day.dir), settings.input.clone()];
+    roots.extend(library_dirs());
+    let mut hash = hash_files(&roots)?;
+    for (name, value) in &settings.params {
+        hash = fnv1a(hash, format!("{name}={value}\n").as_bytes());
+    }
+    Ok(fnv1a(hash, build.as_bytes()))
+}
+
+pub struct ResultCache {
+    pub dir: PathBuf,
+}
+
+impl ResultCache {
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day_{day}.toml"))
+    }
+
+    /// The cached run of `day` if it was stored under `key`. A missing,
+    /// stale or unreadable entry is a miss.
+    pub fn load(&self, day: &Day, key: u64) -> Result<Option<DayRun>, String> {
+        let Ok(text) = fs::read_to_string(self.path(day.year, day.day)) else {
+            return Ok(None);
+        };
+        let Ok(entry) = toml::parse(&text) else {
+            return Ok(None);
+        };
+        if entry.get("", "key").and_then(Value::as_str) != Some(&format!("{key:016x}")) {
+            return Ok(None);
+        }
+
+        let integer = |name: &str| {
+            entry
+                .get("", name)
+                .and_then(Value::as_integer)
+                .and_then(|value| u64::try_from(value).ok())
+        };
+        let time = |stage: Stage| integer(&time_key(stage)).map(Duration::from_nanos);
+        let (Some(parse), Some(part_one), Some(part_two)) = (
+            time(Stage::Parse),
+            time(Stage::PartOne),
+            time(Stage::PartTwo),
+        ) else {
+            return Ok(None);
+        };
+
+        let answers = Answers {
+            part_one: integer("part_one"),
+            part_two: integer("part_two"),
+        };
+        let expected = verify::expected_answers(day)?.unwrap_or_default();
+        Ok(Some(DayRun {
+            year: day.year,
+            day: day.day,
+            answers,
+            timings: Timings {
+                parse,
+                part_one,
+                part_two,
+            },
+            memory: None,
+            verdicts: verify::verify(&expected, &answers),
+            timed_out: [false; 3],
+            cached: true,
+        }))
+    }
+
+    /// Stores `run` under `key`. Runs that timed out have no complete
+    /// result and are not stored.
+    pub fn store(&self, run: &DayRun, key: u64) -> Result<(), String> {
+        if run.timed_out.contains(&true) {
+            return Ok(());
+        }
+        let mut entry = Document::default();
+        entry.set("", "key", Value::String(format!("{key:016x}")));
+        for (name, answer) in [
+            ("part_one", run.answers.part_one),
+            ("part_two", run.answers.part_two),
+        ] {
+            if let Some(answer) = answer {
+                entry.set("", name, Value::Integer(answer.into()));
+            }
+        }
+        for stage in Stage::ALL {
+            let nanos = u64::try_from(run.timings.get(stage).as_nanos()).unwrap_or(u64::MAX);
+            entry.set("", &time_key(stage), Value::Integer(nanos.into()));
+        }
+
+        let path = self.path(run.year, run.day);
+        let write = || {
+            fs::create_dir_all(path.parent().expect("entries live in a year directory"))?;
+            fs::write(&path, entry.to_string())
+        };
+        write().map_err(|error| format!("{}: {error}", path.display()))
+    }
+}
+
+fn time_key(stage: Stage) -> String {
+    format!("{}_ns", stage.name().replace(' ', "_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Params;
+    use crate::run;
+    use crate::solution::tests::{fixture_lines, FIXTURE};
+    use crate::tests::scratch_dir;
+
+    #[test]
+    fn test_hash_changes_with_contents() {
+        let dir = scratch_dir("cache-hash");
+        fs::write(dir.join("input.txt"), "1\n").unwrap();
+        let roots = [dir.clone()];
+        let before = hash_files(&roots).unwrap();
+        assert_eq!(hash_files(&roots).unwrap(), before);
+
+        fs::write(dir.join("input.txt"), "2\n").unwrap();
+        let after = hash_files(&roots).unwrap();
+        assert_ne!(after, before);
+
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("target/build"), "").unwrap();
+        assert_eq!(hash_files(&roots).unwrap(), after);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fingerprint_covers_params_and_build() {
+        let settings = DayConfig {
+            input: scratch_dir("cache-params").join("input.txt"),
+            params: Params::new(),
+        };
+        let plain = fingerprint(&FIXTURE, &settings, "").unwrap();
+        let tuned = DayConfig {
+            params: Params::from([("steps".to_string(), 64)]),
+            ..settings.clone()
+        };
+        assert_ne!(fingerprint(&FIXTURE, &tuned, "").unwrap(), plain);
+        assert_eq!(fingerprint(&FIXTURE, &settings, "").unwrap(), plain);
+        let parallel = fingerprint(&FIXTURE, &settings, "--features parallel").unwrap();
+        assert_ne!(parallel, plain);
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let cache = ResultCache {
+            dir: scratch_dir("cache-entries"),
+        };
+        let fresh = run::run(&FIXTURE, &fixture_lines(), &Params::new(), None).unwrap();
+        assert!(cache.load(&FIXTURE, 7).unwrap().is_none());
+
+        cache.store(&fresh, 7).unwrap();
+        let cached = cache.load(&FIXTURE, 7).unwrap().unwrap();
+        assert!(cached.cached);
+        assert_eq!(cached.answers, fresh.answers);
+        assert_eq!(cached.timings.part_two, fresh.timings.part_two);
+        assert_eq!(cached.verdicts, fresh.verdicts);
+        assert!(cache.load(&FIXTURE, 8).unwrap().is_none());
+
+        let timed_out = DayRun {
+            timed_out: [false, false, true],
+            ..fresh.clone()
+        };
+        cache.store(&timed_out, 9).unwrap();
+        assert!(cache.load(&FIXTURE, 9).unwrap().is_none());
+
+        let mut huge = fresh.clone();
+        huge.answers.part_two = Some(u64::MAX);
+        cache.store(&huge, 10).unwrap();
+        let cached = cache.load(&FIXTURE, 10).unwrap().unwrap();
+        assert_eq!(cached.answers.part_two, Some(u64::MAX));
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+}
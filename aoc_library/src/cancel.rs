@@ -0,0 +1,97 @@
+//! Cooperative cancellation for long-running solutions.
+//!
+//! The runner hands every stage its own [`CancelToken`] and cancels it when
+//! the stage runs past its time limit. Nothing is interrupted: a solution
+//! that may search for a long time polls [`is_cancelled`] and gives up,
+//!
+//! ```ignore
+//! while let Some(state) = queue.pop() {
+//!     if aoc_library::cancel::is_cancelled() {
+//!         return None;
+//!     }
+//!     ...
+//! }
+//! ```
+//!
+//! and one that never checks is abandoned on its thread instead.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag shared between whoever may cancel a computation and the
+/// computation itself.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> CancelToken {
+        CancelToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous token when `with_token` returns or unwinds.
+struct Restore(Option<CancelToken>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+    }
+}
+
+/// Runs `run` with `token` as this thread's current token.
+pub fn with_token<R>(token: &CancelToken, run: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.borrow_mut().replace(token.clone()));
+    let _restore = Restore(previous);
+    run()
+}
+
+/// This thread's current token, to hand on to threads it starts.
+pub fn current() -> Option<CancelToken> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Whether the computation running on this thread has been asked to stop.
+/// Always false outside [`with_token`].
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(CancelToken::is_cancelled)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_token() {
+        assert!(!is_cancelled());
+        assert!(current().is_none());
+
+        let token = CancelToken::new();
+        with_token(&token, || {
+            assert!(!is_cancelled());
+            token.cancel();
+            assert!(is_cancelled());
+            with_token(&CancelToken::new(), || assert!(!is_cancelled()));
+            assert!(is_cancelled());
+        });
+        assert!(!is_cancelled());
+        assert!(current().is_none());
+    }
+}
@@ -0,0 +1,107 @@
+//! Overflow-checked arithmetic for puzzle answers.
+//!
+//! Answers are `u64`. Every value fed into a sum or product carries a label
+//! so that an overflow names the line, card or gear that caused it instead
+//! of silently wrapping.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub item: String,
+}
+
+impl Overflow {
+    pub fn new(item: impl fmt::Display) -> Self {
+        Overflow {
+            item: item.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "u64 overflow at {}", self.item)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Cheap item label that is only formatted when an overflow is reported,
+/// e.g. `Label("card", 12)` displays as `card 12`.
+pub struct Label<T>(pub &'static str, pub T);
+
+impl<T: fmt::Debug> fmt::Display for Label<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}", self.0, self.1)
+    }
+}
+
+pub fn try_sum<L: fmt::Display>(
+    items: impl IntoIterator<Item = (L, u64)>,
+) -> Result<u64, Overflow> {
+    let mut total: u64 = 0;
+    for (label, value) in items {
+        total = total
+            .checked_add(value)
+            .ok_or_else(|| Overflow::new(label))?;
+    }
+    Ok(total)
+}
+
+pub fn try_product<L: fmt::Display>(
+    items: impl IntoIterator<Item = (L, u64)>,
+) -> Result<u64, Overflow> {
+    let mut total: u64 = 1;
+    for (label, value) in items {
+        total = total
+            .checked_mul(value)
+            .ok_or_else(|| Overflow::new(label))?;
+    }
+    Ok(total)
+}
+
+/// Like [`try_sum`], panicking with the offending item on overflow.
+pub fn sum<L: fmt::Display>(items: impl IntoIterator<Item = (L, u64)>) -> u64 {
+    try_sum(items).unwrap_or_else(|overflow| panic!("{overflow}"))
+}
+
+/// Like [`try_product`], panicking with the offending item on overflow.
+pub fn product<L: fmt::Display>(items: impl IntoIterator<Item = (L, u64)>) -> u64 {
+    try_product(items).unwrap_or_else(|overflow| panic!("{overflow}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_and_product() {
+        assert_eq!(sum([("a", 2), ("b", 3)]), 5);
+        assert_eq!(product([("a", 2), ("b", 3)]), 6);
+        assert_eq!(sum(Vec::<(&str, u64)>::new()), 0);
+        assert_eq!(product(Vec::<(&str, u64)>::new()), 1);
+    }
+
+    #[test]
+    fn test_overflow_names_item() {
+        let overflow = try_sum([("line 1", u64::MAX), ("line 2", 1)]).unwrap_err();
+        assert_eq!(overflow.item, "line 2");
+        assert_eq!(overflow.to_string(), "u64 overflow at line 2");
+
+        let overflow = try_product([("red", 1 << 32), ("green", 1 << 32)]).unwrap_err();
+        assert_eq!(overflow.item, "green");
+
+        let overflow = try_sum([
+            (Label("gear at", (3, 4)), u64::MAX),
+            (Label("gear at", (5, 6)), 2),
+        ]);
+        assert_eq!(overflow.unwrap_err().item, "gear at (5, 6)");
+    }
+
+    #[test]
+    #[should_panic(expected = "u64 overflow at card 7")]
+    fn test_sum_panics_with_item() {
+        sum([("card 6", u64::MAX), ("card 7", 1)]);
+    }
+}
@@ -0,0 +1,274 @@
+//! The workspace configuration in `aoc.toml`.
+//!
+//! The file at the workspace root holds the defaults, and a day may keep
+//! an `aoc.toml` of its own next to its `Cargo.toml` that overrides them
+//! for that day:
+//!
+//! ```toml
+//! # <workspace>/aoc.toml
+//! session_file = ".aoc/session"     # relative to the workspace root
+//! cache_dir = ".aoc/cache"
+//! input = "{dir}/input.txt"         # {dir}, {year} and {day} are filled in
+//! verbosity = "normal"              # quiet, normal or verbose
+//!
+//! [2023.day_2.params]
+//! red = 12
+//!
+//! # <workspace>/2023/day_2/aoc.toml
+//! input = "{dir}/input.txt"
+//!
+//! [params]
+//! green = 13
+//! ```
+//!
+//! Every key is optional; without any file the defaults above apply.
+
+use crate::params::{self, InputKind, Params};
+use crate::solution::Day;
+use crate::toml::{self, Document, Value};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// One line of answers per day.
+    Quiet,
+    /// A table of answers and times per day.
+    #[default]
+    Normal,
+    /// The table plus where the input and results came from.
+    Verbose,
+}
+
+impl FromStr for Verbosity {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Verbosity, String> {
+        match text {
+            "quiet" => Ok(Verbosity::Quiet),
+            "normal" => Ok(Verbosity::Normal),
+            "verbose" => Ok(Verbosity::Verbose),
+            _ => Err(format!(
+                "unknown verbosity {text:?}, expected quiet, normal or verbose"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Verbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Verbosity::Quiet => "quiet",
+            Verbosity::Normal => "normal",
+            Verbosity::Verbose => "verbose",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The directory relative paths are resolved against.
+    pub root: PathBuf,
+    pub session_file: PathBuf,
+    pub cache_dir: PathBuf,
+    /// Where a day's input lives, with `{dir}`, `{year}` and `{day}`
+    /// placeholders.
+    pub input: String,
+    pub verbosity: Verbosity,
+    /// The whole root file, for the per-day sections.
+    document: Document,
+}
+
+/// The settings for one day after applying its own `aoc.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayConfig {
+    pub input: PathBuf,
+    pub params: Params,
+}
+
+impl Config {
+    /// The configuration without any file, rooted at `root`.
+    pub fn defaults(root: &Path) -> Config {
+        Config {
+            root: root.to_path_buf(),
+            session_file: root.join(".aoc").join("session"),
+            cache_dir: root.join(".aoc").join("cache"),
+            input: "{dir}/input.txt".to_string(),
+            verbosity: Verbosity::default(),
+            document: Document::default(),
+        }
+    }
+
+    /// Reads `aoc.toml` in `root`, or the defaults if there is none.
+    pub fn load(root: &Path) -> Result<Config, String> {
+        let path = root.join(CONFIG_FILE);
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                Config::parse(root, &text).map_err(|error| format!("{}: {error}", path.display()))
+            }
+            Err(_) if !path.exists() => Ok(Config::defaults(root)),
+            Err(error) => Err(format!("{}: {error}", path.display())),
+        }
+    }
+
+    /// Loads the `aoc.toml` of the nearest directory at or above `start`
+    /// that has one, for binaries that do not know the workspace root.
+    pub fn discover(start: &Path) -> Result<Config, String> {
+        match start
+            .ancestors()
+            .find(|dir| dir.join(CONFIG_FILE).is_file())
+        {
+            Some(root) => Config::load(root),
+            None => Ok(Config::defaults(start)),
+        }
+    }
+
+    pub fn parse(root: &Path, text: &str) -> Result<Config, String> {
+        let document = toml::parse(text).map_err(|error| error.to_string())?;
+        let mut config = Config::defaults(root);
+        if let Some(path) = string(&document, "", "session_file")? {
+            config.session_file = root.join(path);
+        }
+        if let Some(path) = string(&document, "", "cache_dir")? {
+            config.cache_dir = root.join(path);
+        }
+        if let Some(input) = string(&document, "", "input")? {
+            config.input = input.to_string();
+        }
+        if let Some(verbosity) = string(&document, "", "verbosity")? {
+            config.verbosity = verbosity.parse()?;
+        }
+        config.document = document;
+        Ok(config)
+    }
+
+    /// The settings for `day`: its declared parameters for the real input,
+    /// overridden by the root file's `[<year>.day_<day>.params]` and then
+    /// by the day's own `aoc.toml`.
+    pub fn day(&self, day: &Day) -> Result<DayConfig, String> {
+        let section = format!("{}.day_{}.params", day.year, day.day);
+        let mut merged = params::defaults(day.params, InputKind::Real);
+        params::apply(day.params, &mut merged, &table(&self.document, &section)?)?;
+        let mut input = self.input.as_str();
+
+        let path = Path::new(day.dir).join(CONFIG_FILE);
+        let own = match fs::read_to_string(&path) {
+            Ok(text) => {
+                toml::parse(&text).map_err(|error| format!("{}: {error}", path.display()))?
+            }
+            Err(_) if !path.exists() => Document::default(),
+            Err(error) => return Err(format!("{}: {error}", path.display())),
+        };
+        let in_file = |error: String| format!("{}: {error}", path.display());
+        let own_params = table(&own, "params").map_err(in_file)?;
+        params::apply(day.params, &mut merged, &own_params).map_err(in_file)?;
+        if let Some(own_input) = string(&own, "", "input").map_err(in_file)? {
+            input = own_input;
+        }
+
+        let input = input
+            .replace("{dir}", day.dir)
+            .replace("{year}", &day.year.to_string())
+            .replace("{day}", &day.day.to_string());
+        Ok(DayConfig {
+            input: self.root.join(input),
+            params: merged,
+        })
+    }
+}
+
+fn string<'a>(document: &'a Document, section: &str, key: &str) -> Result<Option<&'a str>, String> {
+    document
+        .get(section, key)
+        .map(|value| {
+            value
+                .as_str()
+                .ok_or_else(|| format!("{key} must be a string"))
+        })
+        .transpose()
+}
+
+/// The integers of `section`, e.g. a day's parameters.
+pub fn table(document: &Document, section: &str) -> Result<Params, String> {
+    let Some(entries) = document.section(section) else {
+        return Ok(Params::new());
+    };
+    entries
+        .iter()
+        .map(|(name, value)| match value {
+            Value::Integer(value) => i64::try_from(*value)
+                .map(|value| (name.clone(), value))
+                .map_err(|_| format!("parameter {name} does not fit in i64")),
+            _ => Err(format!("parameter {name} must be an integer")),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::tests::{Fixture, FIXTURE};
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::parse(Path::new("/ws"), "").unwrap();
+        assert_eq!(config, Config::defaults(Path::new("/ws")));
+        assert_eq!(config.session_file, Path::new("/ws/.aoc/session"));
+        assert_eq!(config.verbosity, Verbosity::Normal);
+
+        let day = config.day(&FIXTURE).unwrap();
+        assert_eq!(day.input, Path::new("/ws/fixture/input.txt"));
+        assert_eq!(day.params, Params::from([("steps".to_string(), 64)]));
+    }
+
+    #[test]
+    fn test_parse_settings_and_day_params() {
+        let config = Config::parse(
+            Path::new("/ws"),
+            "cache_dir = \"/tmp/aoc\"\n\
+             input = \"inputs/{year}/{day}.txt\"\n\
+             verbosity = \"quiet\"\n\
+             [2000.day_1.params]\n\
+             steps = 10\n",
+        )
+        .unwrap();
+        assert_eq!(config.cache_dir, Path::new("/tmp/aoc"));
+        assert_eq!(config.verbosity, Verbosity::Quiet);
+
+        let day = config.day(&FIXTURE).unwrap();
+        assert_eq!(day.input, Path::new("/ws/inputs/2000/1.txt"));
+        assert_eq!(day.params, Params::from([("steps".to_string(), 10)]));
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let root = Path::new("/ws");
+        assert!(Config::parse(root, "verbosity = \"loud\"").is_err());
+        assert!(Config::parse(root, "input = 3").is_err());
+        let config = Config::parse(root, "[2000.day_1.params]\nsteps = \"64\"").unwrap();
+        assert!(config.day(&FIXTURE).is_err());
+        let config = Config::parse(root, "[2000.day_1.params]\nstpes = 64").unwrap();
+        assert!(config.day(&FIXTURE).is_err());
+    }
+
+    #[test]
+    fn test_unreadable_day_file_is_an_error() {
+        let dir = crate::tests::scratch_dir("config-day");
+        fs::create_dir(dir.join(CONFIG_FILE)).unwrap();
+        let day = Day::new::<Fixture>(dir.to_string_lossy().into_owned().leak());
+        let config = Config::defaults(Path::new("/ws"));
+        assert!(config.day(&day).unwrap_err().contains(CONFIG_FILE));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_discover_without_file_uses_defaults() {
+        let dir = std::env::temp_dir().join(format!("aoc-config-{}", std::process::id()));
+        let config = Config::discover(&dir).unwrap();
+        assert_eq!(config.root, dir);
+    }
+}
@@ -0,0 +1,196 @@
+//! A multiset: how many times each key occurs.
+//!
+//! Counts are `u64` and a key whose count drops to zero is forgotten, so
+//! two counters are equal exactly when every key has the same count.
+//! Keys are kept in order, which makes iteration and ties in
+//! [`most_common`](Counter::most_common) deterministic.
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<K> {
+    counts: BTreeMap<K, u64>,
+}
+
+impl<K> Default for Counter<K> {
+    fn default() -> Self {
+        Counter {
+            counts: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord + Clone> Counter<K> {
+    pub fn new() -> Self {
+        Counter::default()
+    }
+
+    /// A counter with the given count per key, adding up repeated keys.
+    pub fn from_counts(counts: impl IntoIterator<Item = (K, u64)>) -> Self {
+        let mut counter = Counter::new();
+        for (key, count) in counts {
+            counter.add_n(key, count);
+        }
+        counter
+    }
+
+    pub fn add(&mut self, key: K) {
+        self.add_n(key, 1);
+    }
+
+    pub fn add_n(&mut self, key: K, count: u64) {
+        if count > 0 {
+            *self.counts.entry(key).or_default() += count;
+        }
+    }
+
+    /// Removes up to `count` of `key`, returning how many were removed.
+    pub fn remove_n(&mut self, key: &K, count: u64) -> u64 {
+        let Some(current) = self.counts.get_mut(key) else {
+            return 0;
+        };
+        let removed = count.min(*current);
+        *current -= removed;
+        if *current == 0 {
+            self.counts.remove(key);
+        }
+        removed
+    }
+
+    /// The count of `key`, zero if it never occurred.
+    pub fn get(&self, key: &K) -> u64 {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// The number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The number of occurrences of every key together.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Each key with its count, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, u64)> {
+        self.counts.iter().map(|(key, &count)| (key, count))
+    }
+
+    /// The `n` most frequent keys, most frequent first and equal counts in
+    /// key order.
+    pub fn most_common(&self, n: usize) -> Vec<(&K, u64)> {
+        let mut common: Vec<_> = self.iter().collect();
+        // Stable, so equal counts keep key order.
+        common.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        common.truncate(n);
+        common
+    }
+
+    /// The larger count of each key: the multiset union.
+    pub fn max(&self, other: &Counter<K>) -> Counter<K> {
+        self.combine(other, u64::max)
+    }
+
+    /// The smaller count of each key: the multiset intersection.
+    pub fn min(&self, other: &Counter<K>) -> Counter<K> {
+        self.combine(other, u64::min)
+    }
+
+    /// Both counts of each key added.
+    ///
+    /// # Panics
+    ///
+    /// When a count overflows `u64`.
+    pub fn sum(&self, other: &Counter<K>) -> Counter<K> {
+        self.combine(other, |a, b| {
+            a.checked_add(b)
+                .unwrap_or_else(|| panic!("u64 overflow adding counts {a} and {b}"))
+        })
+    }
+
+    /// The total of [`min`](Counter::min) without building it: how many
+    /// occurrences the two counters share.
+    pub fn intersection_len(&self, other: &Counter<K>) -> u64 {
+        self.iter()
+            .map(|(key, count)| count.min(other.get(key)))
+            .sum()
+    }
+
+    /// Whether every key occurs in `other` at least as often as here.
+    pub fn is_subset(&self, other: &Counter<K>) -> bool {
+        self.iter().all(|(key, count)| count <= other.get(key))
+    }
+
+    fn combine(&self, other: &Counter<K>, count: impl Fn(u64, u64) -> u64) -> Counter<K> {
+        let mut counts = BTreeMap::new();
+        for key in self.counts.keys().chain(other.counts.keys()) {
+            if !counts.contains_key(key) {
+                counts.insert(key.clone(), count(self.get(key), other.get(key)));
+            }
+        }
+        counts.retain(|_, count| *count > 0);
+        Counter { counts }
+    }
+}
+
+impl<K: Ord + Clone> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(keys: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(keys);
+        counter
+    }
+}
+
+impl<K: Ord + Clone> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, keys: I) {
+        for key in keys {
+            self.add(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_and_most_common() {
+        let mut letters: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(letters.get(&'a'), 5);
+        assert_eq!(letters.get(&'z'), 0);
+        assert_eq!((letters.len(), letters.total()), (5, 11));
+        assert_eq!(letters.most_common(3), [(&'a', 5), (&'b', 2), (&'r', 2)]);
+
+        assert_eq!(letters.remove_n(&'c', 4), 1);
+        assert_eq!(letters.len(), 4);
+        letters.add_n('c', 0);
+        assert_eq!(letters.get(&'c'), 0);
+        assert_eq!(
+            letters,
+            Counter::from_counts([('a', 5), ('b', 2), ('d', 1), ('r', 2)])
+        );
+    }
+
+    #[test]
+    fn test_elementwise_operations() {
+        let a = Counter::from_counts([("red", 4), ("blue", 1)]);
+        let b = Counter::from_counts([("red", 2), ("green", 3)]);
+        assert_eq!(
+            a.max(&b),
+            Counter::from_counts([("red", 4), ("blue", 1), ("green", 3)])
+        );
+        assert_eq!(a.min(&b), Counter::from_counts([("red", 2)]));
+        assert_eq!(a.sum(&b).total(), 10);
+        assert_eq!(a.intersection_len(&b), 2);
+        assert_eq!(a.min(&Counter::new()), Counter::new());
+
+        assert!(a.min(&b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert!(Counter::<&str>::new().is_subset(&b));
+    }
+}
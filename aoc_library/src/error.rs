@@ -0,0 +1,49 @@
+//! Where in the input a line failed to parse, and why.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// The 1-based line number, once the error is known to belong to one.
+    pub line: Option<usize>,
+    /// The 1-based column, counted in characters.
+    pub column: usize,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(column: usize, message: impl Into<String>) -> Error {
+        Error {
+            line: None,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn on_line(self, line: usize) -> Error {
+        Error {
+            line: Some(line),
+            ..self
+        }
+    }
+
+    /// The error moved right by `columns`, for an error found in a piece
+    /// of a line that starts after `columns` characters.
+    pub fn shifted(self, columns: usize) -> Error {
+        Error {
+            column: self.column + columns,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}, column {}: {}", self.column, self.message),
+            None => write!(f, "column {}: {}", self.column, self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
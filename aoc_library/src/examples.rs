@@ -0,0 +1,295 @@
+//! Tests generated from example inputs.
+//!
+//! A day crate keeps its examples next to its sources:
+//!
+//! ```text
+//! day_3/examples/puzzle.txt    the example input
+//! day_3/examples/puzzle.toml   part_one = 4361
+//!                              part_two = 467835
+//! ```
+//!
+//! Its `build.rs` calls [`generate_tests`] and its `lib.rs` invokes
+//! [`aoc_examples!`](crate::aoc_examples), which expands to one `#[test]`
+//! per example and answered part. Adding a regression case is adding the
+//! two files. Test names keep only letters and digits, so two examples
+//! whose names differ only in case or punctuation are an error.
+//!
+//! Examples are solved with the example defaults of the day's
+//! [parameters](crate::params); an answers file can set others for its
+//! example in a `[params]` section.
+
+use crate::config;
+use crate::params::{self, InputKind, Params};
+use crate::solution::{Day, Meter, Stage};
+use crate::toml;
+use crate::verify::load_answers;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const GENERATED_FILE: &str = "aoc_examples.rs";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: PathBuf,
+    pub part_one: Option<u64>,
+    pub part_two: Option<u64>,
+    /// Parameters set for this example alone.
+    pub params: Params,
+}
+
+/// Reads every `*.txt` in `dir` with the answers from its `.toml` sibling,
+/// sorted by name. A missing directory has no examples.
+pub fn load_examples(dir: &Path) -> Result<Vec<Example>, String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut examples = Vec::new();
+    for entry in entries {
+        let input = entry.map_err(|error| error.to_string())?.path();
+        if input.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+        let name = input
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| format!("{}: file name is not UTF-8", input.display()))?
+            .to_string();
+
+        let answers_path = input.with_extension("toml");
+        let answers = load_answers(&answers_path)?;
+        let params = example_params(&answers_path)?;
+
+        examples.push(Example {
+            name,
+            input,
+            part_one: answers.part_one,
+            part_two: answers.part_two,
+            params,
+        });
+    }
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    for (index, example) in examples.iter().enumerate() {
+        let id = identifier(&example.name);
+        if let Some(other) = examples[..index]
+            .iter()
+            .find(|other| identifier(&other.name) == id)
+        {
+            return Err(format!(
+                "{}: examples {:?} and {:?} would both be tested as example_{id}; rename one",
+                dir.display(),
+                other.name,
+                example.name
+            ));
+        }
+    }
+    Ok(examples)
+}
+
+fn example_params(path: &Path) -> Result<Params, String> {
+    let in_file = |error: String| format!("{}: {error}", path.display());
+    let text = fs::read_to_string(path).map_err(|error| in_file(error.to_string()))?;
+    let document = toml::parse(&text).map_err(|error| in_file(error.to_string()))?;
+    config::table(&document, "params").map_err(in_file)
+}
+
+/// Turns an example name into a valid test function name fragment.
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+pub fn render_tests(examples: &[Example]) -> String {
+    let mut source = String::new();
+    for example in examples {
+        for (part, stage, answer) in [
+            ("part_one", "PartOne", example.part_one),
+            ("part_two", "PartTwo", example.part_two),
+        ] {
+            let Some(answer) = answer else {
+                continue;
+            };
+            let params: Vec<String> = example
+                .params
+                .iter()
+                .map(|(name, value)| format!("({name:?}, {value})"))
+                .collect();
+            writeln!(
+                source,
+                "#[test]\n\
+                 fn example_{}_{part}() {{\n    \
+                     aoc_library::examples::check(&EXAMPLE_DAY, include_str!({:?}), \
+                     aoc_library::solution::Stage::{stage}, {answer}, &[{}]);\n\
+                 }}\n",
+                identifier(&example.name),
+                example.input.display().to_string(),
+                params.join(", "),
+            )
+            .unwrap();
+        }
+    }
+    source
+}
+
+/// Entry point for a day crate's `build.rs`: writes the tests for its
+/// `examples` directory into `OUT_DIR`.
+pub fn generate_tests() {
+    let manifest_dir =
+        std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let examples_dir = Path::new(&manifest_dir).join("examples");
+
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+    let examples = load_examples(&examples_dir).unwrap_or_else(|error| panic!("{error}"));
+    fs::write(
+        Path::new(&out_dir).join(GENERATED_FILE),
+        render_tests(&examples),
+    )
+    .expect("generated example tests are writable");
+}
+
+/// Runs only the parse stage and `stage`, so an example written for one
+/// part never has to be valid input for the other.
+struct OnlyStage(Stage);
+
+impl Meter for OnlyStage {
+    fn measure(&mut self, stage: Stage, run: &mut dyn FnMut()) {
+        if stage == Stage::Parse || stage == self.0 {
+            run();
+        }
+    }
+}
+
+/// Solves `input` with `day` and asserts the answer for `stage`. The day's
+/// example parameters apply, as changed by `params`.
+pub fn check(day: &Day, input: &str, stage: Stage, expected: u64, params: &[(&str, i64)]) {
+    let lines: Vec<String> = input.lines().map(str::to_string).collect();
+    let mut current = params::defaults(day.params, InputKind::Example);
+    let overrides = params
+        .iter()
+        .map(|&(name, value)| (name.to_string(), value))
+        .collect();
+    params::apply(day.params, &mut current, &overrides).unwrap_or_else(|error| panic!("{error}"));
+    let answers = params::with_params(&current, || day.solve(&lines, &mut OnlyStage(stage)));
+    let actual = match stage {
+        Stage::PartOne => answers.part_one,
+        Stage::PartTwo => answers.part_two,
+        Stage::Parse => panic!("examples check part one or part two"),
+    };
+    assert_eq!(actual, Some(expected), "{day} {stage}");
+}
+
+/// Includes the tests written by [`generate_tests`]. Takes the day's
+/// registered [`Day`] constant.
+#[macro_export]
+macro_rules! aoc_examples {
+    ($day:path) => {
+        #[cfg(test)]
+        mod aoc_examples {
+            #[allow(unused_imports)]
+            use super::*;
+
+            const EXAMPLE_DAY: $crate::Day = $day;
+
+            include!(concat!(env!("OUT_DIR"), "/", "aoc_examples.rs"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::tests::FIXTURE;
+    use crate::tests::scratch_dir;
+
+    #[test]
+    fn test_load_examples_pairs_inputs_with_answers() {
+        let dir = scratch_dir("examples-load");
+        fs::write(dir.join("b.txt"), "1\n2\n").unwrap();
+        fs::write(dir.join("b.toml"), "part_two = 2\n").unwrap();
+        fs::write(dir.join("a-1.txt"), "5\n").unwrap();
+        fs::write(
+            dir.join("a-1.toml"),
+            "part_one = 5\npart_two = 5\n[params]\nsteps = 3\n",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.md"), "ignored").unwrap();
+
+        let examples = load_examples(&dir).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "a-1");
+        assert_eq!(
+            (examples[0].part_one, examples[0].part_two),
+            (Some(5), Some(5))
+        );
+        assert_eq!(
+            (examples[1].part_one, examples[1].part_two),
+            (None, Some(2))
+        );
+
+        let source = render_tests(&examples);
+        assert_eq!(source.matches("#[test]").count(), 3);
+        assert!(source.contains("fn example_a_1_part_one()"));
+        assert!(source.contains("fn example_b_part_two()"));
+        assert!(!source.contains("fn example_b_part_one()"));
+        assert!(source.contains("Stage::PartOne, 5, &[(\"steps\", 3)]);"));
+        assert!(source.contains("Stage::PartTwo, 2, &[]);"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_answers_are_an_error() {
+        let dir = scratch_dir("examples-missing");
+        fs::write(dir.join("lonely.txt"), "1\n").unwrap();
+        assert!(load_examples(&dir).unwrap_err().contains("lonely.toml"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(load_examples(&dir.join("nowhere")), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_examples_with_the_same_test_name_are_an_error() {
+        let dir = scratch_dir("examples-collide");
+        for name in ["a-1", "a_1"] {
+            fs::write(dir.join(format!("{name}.txt")), "1\n").unwrap();
+            fs::write(dir.join(format!("{name}.toml")), "part_one = 1\n").unwrap();
+        }
+        let error = load_examples(&dir).unwrap_err();
+        assert!(
+            error.ends_with(
+                "examples \"a-1\" and \"a_1\" would both be tested as example_a_1; rename one"
+            ),
+            "{error}"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_runs_only_the_requested_part() {
+        check(&FIXTURE, "2\n3\n7\n", Stage::PartOne, 12, &[]);
+        check(&FIXTURE, "2\n3\n7\n", Stage::PartTwo, 42, &[("steps", 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "2000 day 1 part two")]
+    fn test_check_reports_wrong_answer() {
+        check(&FIXTURE, "2\n3\n", Stage::PartTwo, 7, &[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown parameter red")]
+    fn test_check_rejects_undeclared_params() {
+        check(&FIXTURE, "2\n", Stage::PartOne, 2, &[("red", 1)]);
+    }
+}
@@ -0,0 +1,410 @@
+//! A rectangular grid of cells addressed by `(x, y)` positions: `x` is the
+//! zero-based column and `y` the zero-based line, as in the puzzle text.
+//!
+//! Cells other than `char` come from an enum with `#[derive(GridCell)]`,
+//! whose variants name the characters they stand for:
+//!
+//! ```ignore
+//! use aoc_library::grid::{Grid, GridCell};
+//!
+//! #[derive(Clone, Copy, GridCell)]
+//! enum Tile {
+//!     #[cell('.')]
+//!     Open,
+//!     #[cell('#', '@')]
+//!     Wall,
+//! }
+//!
+//! let maze: Grid<Tile> = Grid::parse(&lines)?;
+//! ```
+
+use crate::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub use aoc_macros::GridCell;
+
+/// A `(column, line)` position.
+pub type Pos = (u32, u32);
+
+/// The number of orthogonal steps between two positions.
+pub fn manhattan((ax, ay): Pos, (bx, by): Pos) -> u64 {
+    u64::from(ax.abs_diff(bx)) + u64::from(ay.abs_diff(by))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: u32,
+    height: u32,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A character outside ASCII, at a zero-based `(byte column, line)`.
+    NonAscii { pos: Pos, found: char },
+    /// A character no cell stands for, at a zero-based `(column, line)`.
+    UnknownCell {
+        pos: Pos,
+        found: char,
+        expected: &'static str,
+    },
+    /// A line whose byte length differs from the first line's.
+    RaggedLine {
+        line: u32,
+        expected: u32,
+        found: u32,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::NonAscii { pos: (x, y), found } => write!(
+                f,
+                "non-ASCII character {found:?} at line {}, byte column {}",
+                y + 1,
+                x + 1
+            ),
+            GridError::UnknownCell {
+                pos: (x, y),
+                found,
+                expected,
+            } => write!(
+                f,
+                "unknown character {found:?} at line {}, column {}, expected one of {expected:?}",
+                y + 1,
+                x + 1
+            ),
+            GridError::RaggedLine {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {} is {found} bytes long, expected {expected}",
+                line + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// A cell read from one ASCII character, usually derived.
+pub trait GridCell: Sized {
+    /// Every character some cell stands for.
+    const CHARS: &'static str;
+
+    fn from_char(c: char) -> Option<Self>;
+
+    fn to_char(&self) -> char;
+}
+
+/// The error for a character no `T` stands for.
+pub fn unknown_cell<T: GridCell>(found: char) -> Error {
+    Error::new(
+        1,
+        format!(
+            "unknown character {found:?}, expected one of {:?}",
+            T::CHARS
+        ),
+    )
+}
+
+impl Grid<char> {
+    /// Reads lines of ASCII characters. Every line must be ASCII and as
+    /// long as the first, so byte offsets and columns coincide.
+    pub fn from_ascii(lines: &[String]) -> Result<Self, GridError> {
+        let width = lines.first().map_or(0, String::len);
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in (0_u32..).zip(lines) {
+            if let Some((x, found)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+                return Err(GridError::NonAscii {
+                    pos: (x.try_into().unwrap(), y),
+                    found,
+                });
+            }
+            if line.len() != width {
+                return Err(GridError::RaggedLine {
+                    line: y,
+                    expected: width.try_into().unwrap(),
+                    found: line.len().try_into().unwrap(),
+                });
+            }
+            cells.extend(line.bytes().map(char::from));
+        }
+
+        Ok(Grid {
+            width: width.try_into().unwrap(),
+            height: lines.len().try_into().unwrap(),
+            cells,
+        })
+    }
+}
+
+impl<T: GridCell> Grid<T> {
+    /// Reads lines with one cell per character. Cells are ASCII, so every
+    /// line must be as long in bytes as the first.
+    pub fn parse(lines: &[String]) -> Result<Self, GridError> {
+        let width = lines.first().map_or(0, String::len);
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in (0_u32..).zip(lines) {
+            for (x, found) in (0_u32..).zip(line.chars()) {
+                let cell = T::from_char(found).ok_or(GridError::UnknownCell {
+                    pos: (x, y),
+                    found,
+                    expected: T::CHARS,
+                })?;
+                cells.push(cell);
+            }
+            if line.len() != width {
+                return Err(GridError::RaggedLine {
+                    line: y,
+                    expected: width.try_into().unwrap(),
+                    found: line.len().try_into().unwrap(),
+                });
+            }
+        }
+
+        Ok(Grid {
+            width: width.try_into().unwrap(),
+            height: lines.len().try_into().unwrap(),
+            cells,
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    /// A `width` × `height` grid with every cell set by `cell(pos)`.
+    pub fn from_fn(width: u32, height: u32, mut cell: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut cell)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    fn offset(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos)
+            .then(|| pos.1 as usize * self.width as usize + pos.0 as usize)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.offset(pos).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.offset(pos).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Line `y` as a slice.
+    pub fn row(&self, y: u32) -> &[T] {
+        let start = y as usize * self.width as usize;
+        &self.cells[start..start + self.width as usize]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Every cell with its position, line by line.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        let width = self.width.max(1);
+        (0_u32..)
+            .zip(&self.cells)
+            .map(move |(offset, cell)| ((offset % width, offset / width), cell))
+    }
+
+    /// The position of the first cell, line by line, that matches.
+    pub fn position(&self, mut matches: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| matches(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Positions of the up to four orthogonal neighbours inside the grid.
+    pub fn neighbours(&self, (x, y): Pos) -> impl Iterator<Item = Pos> + '_ {
+        [(0, -1), (-1, 0), (1, 0), (0, 1)]
+            .into_iter()
+            .filter_map(move |delta| self.step((x, y), delta))
+    }
+
+    /// Positions of the up to eight neighbours inside the grid, diagonals
+    /// included.
+    pub fn neighbours8(&self, (x, y): Pos) -> impl Iterator<Item = Pos> + '_ {
+        [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ]
+        .into_iter()
+        .filter_map(move |delta| self.step((x, y), delta))
+    }
+
+    /// `pos` moved by `(dx, dy)`, if that is still inside the grid.
+    pub fn step(&self, (x, y): Pos, (dx, dy): (i64, i64)) -> Option<Pos> {
+        let x = u32::try_from(i64::from(x) + dx).ok()?;
+        let y = u32::try_from(i64::from(y) + dy).ok()?;
+        self.contains((x, y)).then_some((x, y))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside a {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside a {width}x{height} grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn test_from_ascii_and_lookup() {
+        let grid = Grid::from_ascii(&lines(&["ab.", "#cd"])).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((0, 1)), Some(&'#'));
+        assert_eq!(grid[(2, 1)], 'd');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.row(1), &['#', 'c', 'd']);
+        assert_eq!(grid.to_string(), "ab.\n#cd\n");
+        assert_eq!(
+            grid.iter().find(|(_, &c)| c == 'c').map(|(pos, _)| pos),
+            Some((1, 1))
+        );
+    }
+
+    #[test]
+    fn test_from_ascii_errors() {
+        assert_eq!(
+            Grid::from_ascii(&lines(&["ab", "é."])),
+            Err(GridError::NonAscii {
+                pos: (0, 1),
+                found: 'é'
+            })
+        );
+        assert_eq!(
+            Grid::from_ascii(&lines(&["ab", "abc"]))
+                .unwrap_err()
+                .to_string(),
+            "line 2 is 3 bytes long, expected 2"
+        );
+        assert_eq!(Grid::from_ascii(&[]).unwrap().width(), 0);
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, GridCell)]
+    enum Tile {
+        #[cell('.')]
+        Open,
+        #[cell('#', '@')]
+        Wall,
+        #[cell('0'..='9')]
+        Marker(char),
+    }
+
+    #[test]
+    fn test_derived_cells() {
+        assert_eq!(Tile::try_from('@'), Ok(Tile::Wall));
+        assert_eq!(Tile::try_from('7'), Ok(Tile::Marker('7')));
+        assert_eq!(
+            Tile::try_from('x').unwrap_err().to_string(),
+            "column 1: unknown character 'x', expected one of \".#@0123456789\""
+        );
+        assert_eq!(Tile::Wall.to_string(), "#");
+        assert_eq!(Tile::Marker('3').to_string(), "3");
+    }
+
+    #[test]
+    fn test_parse_cells() {
+        let grid: Grid<Tile> = Grid::parse(&lines(&["#.1", "@.."])).unwrap();
+        assert_eq!(grid[(2, 0)], Tile::Marker('1'));
+        assert_eq!(grid[(0, 1)], Tile::Wall);
+        assert_eq!(grid.to_string(), "#.1\n#..\n");
+
+        assert_eq!(
+            Grid::<Tile>::parse(&lines(&["#..", ".x."])),
+            Err(GridError::UnknownCell {
+                pos: (1, 1),
+                found: 'x',
+                expected: ".#@0123456789",
+            })
+        );
+        assert_eq!(
+            Grid::<Tile>::parse(&lines(&["#..", ".é"]))
+                .unwrap_err()
+                .to_string(),
+            "unknown character 'é' at line 2, column 2, expected one of \".#@0123456789\""
+        );
+        assert!(matches!(
+            Grid::<Tile>::parse(&lines(&["#..", ".."])),
+            Err(GridError::RaggedLine { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_neighbours_stay_inside() {
+        let grid = Grid::from_fn(3, 3, |(x, y)| x + y);
+        assert_eq!(grid[(2, 2)], 4);
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours((2, 1)).count(), 3);
+        assert_eq!(grid.step((0, 0), (-1, 0)), None);
+        assert_eq!(grid.position(|&sum| sum == 3), Some((2, 1)));
+        assert_eq!(manhattan((0, 2), (3, 1)), 4);
+    }
+}
@@ -0,0 +1,292 @@
+//! Local benchmark history and regression detection.
+//!
+//! Each benchmarked stage is appended as one tab-separated line:
+//!
+//! ```text
+//! commit  machine  unix_time  year  day  stage  mean_ns  median_ns  min_ns  stddev_ns
+//! ```
+
+use crate::bench::{BenchReport, Stats};
+use crate::solution::{Day, Stage};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub commit: String,
+    pub machine: String,
+    pub unix_time: u64,
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+impl Record {
+    pub fn from_report(
+        day: &Day,
+        report: &BenchReport,
+        commit: &str,
+        machine: &str,
+    ) -> Vec<Record> {
+        let unix_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        Stage::ALL
+            .iter()
+            .map(|&stage| Record {
+                commit: commit.to_string(),
+                machine: machine.to_string(),
+                unix_time,
+                year: day.year,
+                day: day.day,
+                stage,
+                stats: report.get(stage),
+            })
+            .collect()
+    }
+
+    fn to_line(&self) -> String {
+        let stats = &self.stats;
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.commit,
+            self.machine,
+            self.unix_time,
+            self.year,
+            self.day,
+            stage_key(self.stage),
+            stats.mean.as_nanos(),
+            stats.median.as_nanos(),
+            stats.min.as_nanos(),
+            stats.stddev.as_nanos(),
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Record> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [commit, machine, unix_time, year, day, stage, mean, median, min, stddev] =
+            fields.as_slice()
+        else {
+            return None;
+        };
+        let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+
+        Some(Record {
+            commit: commit.to_string(),
+            machine: machine.to_string(),
+            unix_time: unix_time.parse().ok()?,
+            year: year.parse().ok()?,
+            day: day.parse().ok()?,
+            stage: Stage::ALL.into_iter().find(|s| stage_key(*s) == *stage)?,
+            stats: Stats {
+                mean: nanos(mean)?,
+                median: nanos(median)?,
+                min: nanos(min)?,
+                stddev: nanos(stddev)?,
+            },
+        })
+    }
+}
+
+fn stage_key(stage: Stage) -> &'static str {
+    match stage {
+        Stage::Parse => "parse",
+        Stage::PartOne => "part_one",
+        Stage::PartTwo => "part_two",
+    }
+}
+
+/// Reads every well-formed record; a missing file is an empty history.
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents.lines().filter_map(Record::from_line).collect()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn append(path: &Path, records: &[Record]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(file, "{}", record.to_line())?;
+    }
+    Ok(())
+}
+
+/// The current short commit hash, suffixed with `-dirty` when the working
+/// tree has changes, or `unknown` outside a git checkout.
+pub fn git_commit(dir: &Path) -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => {
+            format!("{commit}-dirty")
+        }
+        Some(commit) => commit,
+        None => "unknown".to_string(),
+    }
+}
+
+/// Host name plus OS and architecture, e.g. `builder-linux-x86_64`.
+pub fn machine() -> String {
+    let host = std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    format!("{host}-{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline_commit: String,
+    pub before: Duration,
+    pub after: Duration,
+    /// Slowdown in percent of the baseline median.
+    pub slowdown: f64,
+}
+
+/// The most recent record of a stage benchmarked on `machine`.
+pub fn latest<'a>(
+    history: &'a [Record],
+    machine: &str,
+    year: u16,
+    day: u8,
+    stage: Stage,
+) -> Option<&'a Record> {
+    history.iter().rev().find(|record| {
+        record.machine == machine
+            && record.year == year
+            && record.day == day
+            && record.stage == stage
+    })
+}
+
+/// Compares medians of `current` against the most recent earlier record
+/// for the same machine, day and stage, flagging slowdowns above
+/// `threshold_percent`.
+pub fn compare(history: &[Record], current: &[Record], threshold_percent: f64) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+
+    for record in current {
+        let baseline = latest(
+            history,
+            &record.machine,
+            record.year,
+            record.day,
+            record.stage,
+        );
+        let Some(baseline) = baseline else {
+            continue;
+        };
+
+        let before = baseline.stats.median.as_secs_f64();
+        let after = record.stats.median.as_secs_f64();
+        if before <= 0.0 {
+            continue;
+        }
+        let slowdown = (after - before) / before * 100.0;
+        if slowdown > threshold_percent {
+            regressions.push(Regression {
+                year: record.year,
+                day: record.day,
+                stage: record.stage,
+                baseline_commit: baseline.commit.clone(),
+                before: baseline.stats.median,
+                after: record.stats.median,
+                slowdown,
+            });
+        }
+    }
+
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(commit: &str, machine: &str, stage: Stage, median_us: u64) -> Record {
+        let median = Duration::from_micros(median_us);
+        Record {
+            commit: commit.to_string(),
+            machine: machine.to_string(),
+            unix_time: 1_700_000_000,
+            year: 2023,
+            day: 3,
+            stage,
+            stats: Stats {
+                mean: median,
+                median,
+                min: median,
+                stddev: Duration::ZERO,
+            },
+        }
+    }
+
+    #[test]
+    fn test_record_round_trips_through_line() {
+        let original = record("abc123-dirty", "host-linux-x86_64", Stage::PartTwo, 1500);
+        assert_eq!(Record::from_line(&original.to_line()), Some(original));
+        assert_eq!(Record::from_line("garbage"), None);
+    }
+
+    #[test]
+    fn test_load_and_append() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.tsv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        assert!(load(&path).unwrap().is_empty());
+
+        let records = vec![
+            record("a", "m", Stage::Parse, 10),
+            record("a", "m", Stage::PartOne, 20),
+        ];
+        append(&path, &records).unwrap();
+        append(&path, &records[..1]).unwrap();
+        assert_eq!(load(&path).unwrap().len(), 3);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_compare_flags_slowdowns_above_threshold() {
+        let history = vec![
+            record("old", "m", Stage::Parse, 100),
+            record("old", "m", Stage::PartOne, 100),
+            record("newer", "m", Stage::PartOne, 200),
+            record("other", "elsewhere", Stage::PartTwo, 1),
+        ];
+        let current = vec![
+            record("new", "m", Stage::Parse, 105),
+            record("new", "m", Stage::PartOne, 300),
+            record("new", "m", Stage::PartTwo, 900),
+        ];
+
+        let regressions = compare(&history, &current, 10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].stage, Stage::PartOne);
+        assert_eq!(regressions[0].baseline_commit, "newer");
+        assert!((regressions[0].slowdown - 50.0).abs() < 1e-6);
+
+        assert_eq!(compare(&history, &current, 2.0).len(), 2);
+    }
+}
@@ -0,0 +1,276 @@
+//! Just enough HTML handling for puzzle pages: rendering the puzzle text as
+//! markdown and pulling out the `<pre><code>` example blocks.
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Text(&'a str),
+    Open(String),
+    Close(String),
+}
+
+/// Splits `html` into text and tags. Comments, doctypes and attributes are
+/// dropped; tag names are lowercased.
+fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            tokens.push(Token::Text(rest));
+            return tokens;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        let name = |tag: &str| {
+            tag.split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase()
+        };
+        if let Some(closing) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name(closing)));
+        } else if !tag.starts_with('!') {
+            tokens.push(Token::Open(name(tag)));
+        }
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    tokens
+}
+
+/// Replaces the character references that appear in puzzle pages.
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((end, decode_entity(&rest[1..end])?)));
+        match entity {
+            Some((end, c)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/// The puzzle descriptions of a page: the contents of its `<article>`
+/// elements, or the whole page when it has none.
+fn articles<'a>(tokens: &'a [Token<'a>]) -> Vec<&'a Token<'a>> {
+    let has_articles = tokens.contains(&Token::Open("article".to_string()));
+    let mut depth = 0;
+    tokens
+        .iter()
+        .filter(|token| {
+            if !has_articles {
+                return true;
+            }
+            match token {
+                Token::Open(name) if name == "article" => depth += 1,
+                Token::Close(name) if name == "article" => depth -= 1,
+                _ => (),
+            }
+            depth > 0
+        })
+        .collect()
+}
+
+/// Renders the puzzle description as markdown for reading in a terminal:
+/// headings, paragraphs, emphasis, inline code, lists and fenced example
+/// blocks. Links keep only their text.
+pub fn to_markdown(html: &str) -> String {
+    let tokens = tokens(html);
+    let mut markdown = String::new();
+    let mut in_pre = false;
+
+    for token in articles(&tokens) {
+        match token {
+            Token::Text(text) if in_pre => markdown.push_str(&decode_entities(text)),
+            Token::Text(text) => {
+                let text = decode_entities(text);
+                let mut words = text.split_whitespace().peekable();
+                if text.starts_with(char::is_whitespace) && words.peek().is_some() {
+                    push_space(&mut markdown);
+                }
+                while let Some(word) = words.next() {
+                    markdown.push_str(word);
+                    if words.peek().is_some() {
+                        markdown.push(' ');
+                    }
+                }
+                if text.ends_with(char::is_whitespace) {
+                    push_space(&mut markdown);
+                }
+            }
+            Token::Open(_) if in_pre => (),
+            Token::Close(name) if in_pre => {
+                if name == "pre" {
+                    in_pre = false;
+                    if !markdown.ends_with('\n') {
+                        markdown.push('\n');
+                    }
+                    markdown.push_str("```\n\n");
+                }
+            }
+            Token::Open(name) => match name.as_str() {
+                "h1" | "h2" | "h3" => {
+                    end_block(&mut markdown);
+                    markdown.push_str("## ");
+                }
+                "p" | "ul" | "ol" => end_block(&mut markdown),
+                "pre" => {
+                    end_block(&mut markdown);
+                    markdown.push_str("```\n");
+                    in_pre = true;
+                }
+                "li" => markdown.push_str("- "),
+                "em" => markdown.push('*'),
+                "code" => markdown.push('`'),
+                "br" => markdown.push('\n'),
+                _ => (),
+            },
+            Token::Close(name) => match name.as_str() {
+                "h1" | "h2" | "h3" | "p" | "ul" | "ol" => end_block(&mut markdown),
+                "li" => {
+                    trim_end_spaces(&mut markdown);
+                    markdown.push('\n');
+                }
+                "em" => {
+                    trim_end_spaces(&mut markdown);
+                    markdown.push('*');
+                }
+                "code" => markdown.push('`'),
+                _ => (),
+            },
+        }
+    }
+
+    markdown.trim().to_string() + "\n"
+}
+
+fn trim_end_spaces(markdown: &mut String) {
+    markdown.truncate(markdown.trim_end_matches(' ').len());
+}
+
+fn push_space(markdown: &mut String) {
+    if !markdown.is_empty() && !markdown.ends_with([' ', '\n']) {
+        markdown.push(' ');
+    }
+}
+
+/// Ends the current block with exactly one blank line.
+fn end_block(markdown: &mut String) {
+    markdown.truncate(markdown.trim_end().len());
+    if !markdown.is_empty() {
+        markdown.push_str("\n\n");
+    }
+}
+
+/// The text of every `<pre>` block in the puzzle descriptions, entities
+/// decoded and markup such as `<em>` dropped, each ending in a newline.
+pub fn code_blocks(html: &str) -> Vec<String> {
+    let tokens = tokens(html);
+    let mut blocks = Vec::new();
+    let mut current: Option<String> = None;
+
+    for token in articles(&tokens) {
+        match (token, &mut current) {
+            (Token::Open(name), None) if name == "pre" => current = Some(String::new()),
+            (Token::Close(name), Some(block)) if name == "pre" => {
+                if !block.ends_with('\n') {
+                    block.push('\n');
+                }
+                blocks.push(std::mem::take(block));
+                current = None;
+            }
+            (Token::Text(text), Some(block)) => block.push_str(&decode_entities(text)),
+            _ => (),
+        }
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<!DOCTYPE html>\n<html><head><title>Day 4</title></head><body>\
+        <header><h1><a href=\"/\">Advent of Code</a></h1></header>\
+        <main><article class=\"day-desc\"><h2>--- Day 4: Scratchcards ---</h2>\n\
+        <p>The Elf leads you over to the <em>pile</em> of colorful cards.\n\
+        Each card has <code>winning numbers</code> &amp; numbers you have.</p>\n\
+        <!-- a comment -->\
+        <pre><code>Card 1: 41 48 | 83 <em>48</em>\nCard 2: 13 32 | 61 &lt;30&gt;\n</code></pre>\n\
+        <ul><li>Card 1 is worth <em>8</em> points.</li><li>Card 2 is not.</li></ul>\n\
+        </article><p>Answer: <input/></p></main></body></html>";
+
+    #[test]
+    fn test_to_markdown() {
+        assert_eq!(
+            to_markdown(PAGE),
+            "## --- Day 4: Scratchcards ---\n\n\
+             The Elf leads you over to the *pile* of colorful cards. \
+             Each card has `winning numbers` & numbers you have.\n\n\
+             ```\nCard 1: 41 48 | 83 48\nCard 2: 13 32 | 61 <30>\n```\n\n\
+             - Card 1 is worth *8* points.\n- Card 2 is not.\n"
+        );
+    }
+
+    #[test]
+    fn test_code_blocks() {
+        assert_eq!(
+            code_blocks(PAGE),
+            ["Card 1: 41 48 | 83 48\nCard 2: 13 32 | 61 <30>\n"]
+        );
+        assert_eq!(
+            code_blocks("<pre>a</pre><p>b</p><pre>c\n</pre>"),
+            ["a\n", "c\n"]
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &amp;&amp; &#65;&#x42; &bogus; & done"),
+            "<a> && AB &bogus; & done"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! The HTTP backend used to talk to adventofcode.com.
+//!
+//! Requests go through the [`HttpBackend`] trait so the transport can be
+//! swapped: [`Curl`] shells out to the system `curl`, and [`LocalBackend`]
+//! serves canned pages for tests and offline use.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const USER_AGENT: &str = "aoc_library puzzle fetcher";
+
+pub trait HttpBackend {
+    /// GETs `url` and returns the body. `cookie` is sent as the `Cookie`
+    /// header. Non-success statuses are errors.
+    fn get(&self, url: &str, cookie: Option<&str>) -> Result<String, String>;
+}
+
+/// Fetches with the `curl` binary on `PATH`.
+pub struct Curl;
+
+impl HttpBackend for Curl {
+    fn get(&self, url: &str, cookie: Option<&str>) -> Result<String, String> {
+        // Headers are read from stdin so the cookie never shows up in the
+        // process list.
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--location"])
+            .args(["--user-agent", USER_AGENT, "--header", "@-", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| format!("running curl: {error}"))?;
+
+        let headers = cookie.map_or(String::new(), |cookie| format!("Cookie: {cookie}\n"));
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(headers.as_bytes())
+            .map_err(|error| format!("writing curl headers: {error}"))?;
+
+        let output = child
+            .wait_with_output()
+            .map_err(|error| format!("running curl: {error}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "{url}: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        String::from_utf8(output.stdout).map_err(|_| format!("{url}: response is not UTF-8"))
+    }
+}
+
+/// Serves pages registered with [`LocalBackend::insert`] and records every
+/// URL requested. Unknown URLs fail like a 404.
+#[derive(Default)]
+pub struct LocalBackend {
+    pages: BTreeMap<String, String>,
+    requests: RefCell<Vec<String>>,
+}
+
+impl LocalBackend {
+    pub fn insert(&mut self, url: &str, body: &str) {
+        self.pages.insert(url.to_string(), body.to_string());
+    }
+
+    /// The URLs requested so far, oldest first.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.borrow().clone()
+    }
+}
+
+impl HttpBackend for LocalBackend {
+    fn get(&self, url: &str, _cookie: Option<&str>) -> Result<String, String> {
+        self.requests.borrow_mut().push(url.to_string());
+        self.pages
+            .get(url)
+            .cloned()
+            .ok_or_else(|| format!("{url}: 404 Not Found"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_backend_serves_and_records() {
+        let mut backend = LocalBackend::default();
+        backend.insert("https://example.test/a", "body");
+        assert_eq!(
+            backend.get("https://example.test/a", None),
+            Ok("body".to_string())
+        );
+        assert!(backend
+            .get("https://example.test/b", Some("session=x"))
+            .unwrap_err()
+            .contains("404"));
+        assert_eq!(
+            backend.requests(),
+            ["https://example.test/a", "https://example.test/b"]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Integers pulled out of noisy lines such as `"Card   1: 13  4 61"` or
+//! `"x=-3, y=12"`, ignoring whatever surrounds them.
+//!
+//! Digits run together into one integer. For signed types a `-` right
+//! before the digits makes it negative; for unsigned types the `-` is just
+//! noise, so `"1-3"` reads as `1` and `3` into `u32` but `1` and `-3` into
+//! `i32`. [`Ints::strict`] instead reads a field that must hold nothing
+//! but integers. Nothing here allocates unless the caller collects.
+//!
+//! ```
+//! use aoc_library::ints;
+//!
+//! let [id, x, y] = ints::ints::<3>("Sensor 2 at x=-3, y=12")?;
+//! assert_eq!((id, x, y), (2, -3, 12));
+//!
+//! let mut halves = ints::per_field::<u32>("Card 1: 41 48 | 83 86  6", "|");
+//! assert_eq!(halves.next().unwrap().count(), 3);
+//! # Ok::<(), aoc_library::Error>(())
+//! ```
+
+use crate::error::Error;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// An integer type the helpers can read.
+pub trait Int: Copy + Default + FromStr + 'static {
+    const SIGNED: bool;
+}
+
+macro_rules! ints {
+    ($signed:literal: $($ty:ty),*) => {$(
+        impl Int for $ty {
+            const SIGNED: bool = $signed;
+        }
+    )*};
+}
+
+ints!(false: u8, u16, u32, u64, u128, usize);
+ints!(true: i8, i16, i32, i64, i128, isize);
+
+/// An integer and the byte range of the line it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Located<T> {
+    pub value: T,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// The integers in part of a line, in order. Yields an error for one too
+/// large for `T`.
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    line: &'a str,
+    position: usize,
+    end: usize,
+    kind: PhantomData<T>,
+}
+
+impl<'a, T: Int> Ints<'a, T> {
+    fn new(line: &'a str, start: usize, end: usize) -> Ints<'a, T> {
+        Ints {
+            line,
+            position: start,
+            end,
+            kind: PhantomData,
+        }
+    }
+
+    /// The next integer with where it is.
+    pub fn next_located(&mut self) -> Option<Result<Located<T>, Error>> {
+        let bytes = &self.line.as_bytes()[..self.end];
+        let digits = self.position + bytes[self.position..].iter().position(u8::is_ascii_digit)?;
+        let start = if T::SIGNED && digits > self.position && bytes[digits - 1] == b'-' {
+            digits - 1
+        } else {
+            digits
+        };
+        let end = digits
+            + bytes[digits..]
+                .iter()
+                .position(|byte| !byte.is_ascii_digit())
+                .unwrap_or(bytes.len() - digits);
+        self.position = end;
+
+        let text = &self.line[start..end];
+        Some(match text.parse() {
+            Ok(value) => Ok(Located { value, start, end }),
+            Err(_) => Err(Error::new(
+                column(self.line, start),
+                format!("{text} does not fit in {}", std::any::type_name::<T>()),
+            )),
+        })
+    }
+
+    /// The remaining integers, with nothing but whitespace allowed between
+    /// them: `"4 x6"` is an error at `x6` instead of 4 and 6.
+    pub fn strict(self) -> impl Iterator<Item = Result<T, Error>> + 'a {
+        let line = self.line;
+        line[self.position..self.end]
+            .split_whitespace()
+            .map(move |word| {
+                let start = word.as_ptr() as usize - line.as_ptr() as usize;
+                word.parse().map_err(|_| {
+                    Error::new(
+                        column(line, start),
+                        format!("expected {}, found {word:?}", std::any::type_name::<T>()),
+                    )
+                })
+            })
+    }
+
+    /// Every remaining integer with where it is.
+    pub fn located(mut self) -> impl Iterator<Item = Result<Located<T>, Error>> + 'a {
+        std::iter::from_fn(move || self.next_located())
+    }
+
+    /// Fills an array with the remaining integers, which must number
+    /// exactly `N`.
+    pub fn array<const N: usize>(mut self) -> Result<[T; N], Error> {
+        let mut values = [T::default(); N];
+        for (count, value) in values.iter_mut().enumerate() {
+            *value = match self.next_located() {
+                Some(found) => found?.value,
+                None => {
+                    return Err(Error::new(
+                        column(self.line, self.end),
+                        format!("expected {}, found {count}", integers(N)),
+                    ))
+                }
+            };
+        }
+        match self.next_located() {
+            None => Ok(values),
+            Some(extra) => {
+                let start =
+                    extra.map_or_else(|error| error.column, |extra| column(self.line, extra.start));
+                Err(Error::new(
+                    start,
+                    format!("expected {}, found more", integers(N)),
+                ))
+            }
+        }
+    }
+}
+
+impl<T: Int> Iterator for Ints<'_, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Result<T, Error>> {
+        self.next_located()
+            .map(|found| found.map(|found| found.value))
+    }
+}
+
+fn integers(count: usize) -> String {
+    match count {
+        1 => "1 integer".to_string(),
+        _ => format!("{count} integers"),
+    }
+}
+
+/// The 1-based column of byte `offset`.
+fn column(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+/// Every integer in `line`.
+pub fn all<T: Int>(line: &str) -> Ints<'_, T> {
+    Ints::new(line, 0, line.len())
+}
+
+/// The integers of each part of `line` between `separator`s, with
+/// positions still counted in the whole line.
+pub fn per_field<'a, T: Int>(
+    line: &'a str,
+    separator: &'a str,
+) -> impl Iterator<Item = Ints<'a, T>> + 'a {
+    line.split(separator).map(move |field| {
+        let start = field.as_ptr() as usize - line.as_ptr() as usize;
+        Ints::new(line, start, start + field.len())
+    })
+}
+
+/// Exactly `N` signed integers from `line`.
+pub fn ints<const N: usize>(line: &str) -> Result<[i64; N], Error> {
+    all(line).array()
+}
+
+/// Exactly `N` unsigned integers from `line`; a `-` is not a sign.
+pub fn uints<const N: usize>(line: &str) -> Result<[u64; N], Error> {
+    all(line).array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_with_positions() {
+        let values: Vec<i32> = all("x=-3, y=12..-7").map(Result::unwrap).collect();
+        assert_eq!(values, [-3, 12, -7]);
+        let values: Vec<u32> = all("x=-3, y=12..-7").map(Result::unwrap).collect();
+        assert_eq!(values, [3, 12, 7]);
+        assert_eq!(
+            all::<i64>("1-3").map(Result::unwrap).collect::<Vec<_>>(),
+            [1, -3]
+        );
+        assert_eq!(all::<u8>("no numbers - here").count(), 0);
+
+        let found: Vec<_> = all::<u64>("Card   1: 13  4")
+            .located()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            found,
+            [
+                Located {
+                    value: 1,
+                    start: 7,
+                    end: 8
+                },
+                Located {
+                    value: 13,
+                    start: 10,
+                    end: 12
+                },
+                Located {
+                    value: 4,
+                    start: 14,
+                    end: 15
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_per_field() {
+        let line = "Card 1: 41 48 | 83 86  6";
+        let fields: Vec<Vec<u32>> = per_field(line, "|")
+            .map(|field| field.map(Result::unwrap).collect())
+            .collect();
+        assert_eq!(fields, [vec![1, 41, 48], vec![83, 86, 6]]);
+
+        let second = per_field::<u32>(line, "|").nth(1).unwrap();
+        assert_eq!(second.located().next().unwrap().unwrap().start, 16);
+    }
+
+    #[test]
+    fn test_strict() {
+        let line = "Card 1: 41 48 | 83 x6";
+        let values: Result<Vec<u32>, _> = all(&line[7..13]).strict().collect();
+        assert_eq!(values, Ok(vec![41, 48]));
+        let error = per_field::<u32>(line, "|")
+            .nth(1)
+            .unwrap()
+            .strict()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!(error.to_string(), "column 20: expected u32, found \"x6\"");
+        assert!(all::<u32>("-3").strict().next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_fixed_count() {
+        assert_eq!(ints::<3>("p=0,4 v=-3"), Ok([0, 4, -3]));
+        assert_eq!(uints::<2>("3   4"), Ok([3, 4]));
+        assert_eq!(
+            ints::<3>("p=0,4").unwrap_err().to_string(),
+            "column 6: expected 3 integers, found 2"
+        );
+        assert_eq!(
+            uints::<1>("é 1 2").unwrap_err().to_string(),
+            "column 5: expected 1 integer, found more"
+        );
+        assert_eq!(
+            all::<u8>("7 300").array::<2>().unwrap_err().to_string(),
+            "column 3: 300 does not fit in u8"
+        );
+    }
+}
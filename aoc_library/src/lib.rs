@@ -0,0 +1,61 @@
+pub mod bench;
+pub mod cache;
+pub mod cancel;
+pub mod checked;
+pub mod config;
+pub mod counter;
+pub mod error;
+pub mod examples;
+pub mod grid;
+pub mod history;
+pub mod html;
+pub mod http;
+pub mod ints;
+pub mod json;
+pub mod memory;
+pub mod parallel;
+pub mod params;
+pub mod parse;
+pub mod puzzle;
+pub mod report;
+pub mod run;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod toml;
+pub mod verify;
+pub mod watch;
+
+// Lets the derive macros name `::aoc_library` inside this crate too.
+extern crate self as aoc_library;
+
+pub use error::Error;
+pub use solution::{Day, Solution};
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+pub fn read_file_to_vec(path: &str) -> Result<Vec<String>, std::io::Error> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    reader.lines().collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// An empty directory of this test process, named after `name`.
+    pub fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_read_missing_file_is_an_error() {
+        assert!(read_file_to_vec("does/not/exist.txt").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! Opt-in heap accounting through a counting global allocator.
+//!
+//! A binary opts in by installing the allocator:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: aoc_library::memory::CountingAllocator =
+//!     aoc_library::memory::CountingAllocator;
+//! ```
+//!
+//! Without it the counters stay at zero and [`is_active`] is false.
+
+use crate::solution::{Meter, Stage};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Forwards to the system allocator while counting allocations, bytes
+/// allocated, and the current and peak heap size.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn record_alloc(size: usize) {
+        ACTIVE.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: every call is forwarded unchanged to `System`; the counters are
+// only bookkeeping.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// True once a [`CountingAllocator`] has served an allocation.
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Usage {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Highest heap size reached, above the size when measuring started.
+    pub peak: u64,
+}
+
+/// Counts what `run` allocates. Counters are process-wide, so other
+/// threads allocating at the same time are included.
+pub fn measure<T>(run: impl FnOnce() -> T) -> (T, Usage) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES_ALLOCATED.load(Ordering::Relaxed);
+    let start = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(start, Ordering::Relaxed);
+
+    let result = run();
+
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES_ALLOCATED.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(start) as u64,
+    };
+    (result, usage)
+}
+
+/// Records the heap usage of each stage while delegating the run itself
+/// to `inner`, e.g. [`Timings`](crate::solution::Timings).
+pub struct MemoryMeter<'a> {
+    pub inner: &'a mut dyn Meter,
+    pub parse: Usage,
+    pub part_one: Usage,
+    pub part_two: Usage,
+}
+
+impl<'a> MemoryMeter<'a> {
+    pub fn new(inner: &'a mut dyn Meter) -> Self {
+        MemoryMeter {
+            inner,
+            parse: Usage::default(),
+            part_one: Usage::default(),
+            part_two: Usage::default(),
+        }
+    }
+
+    pub fn get(&self, stage: Stage) -> Usage {
+        match stage {
+            Stage::Parse => self.parse,
+            Stage::PartOne => self.part_one,
+            Stage::PartTwo => self.part_two,
+        }
+    }
+}
+
+impl Meter for MemoryMeter<'_> {
+    fn measure(&mut self, stage: Stage, run: &mut dyn FnMut()) {
+        let inner = &mut *self.inner;
+        let ((), usage) = measure(|| inner.measure(stage, run));
+        match stage {
+            Stage::Parse => self.parse = usage,
+            Stage::PartOne => self.part_one = usage,
+            Stage::PartTwo => self.part_two = usage,
+        }
+    }
+
+    fn answer(&mut self, stage: Stage, answer: Option<u64>) {
+        self.inner.answer(stage, answer);
+    }
+}
+
+/// Human-readable byte count using binary units, e.g. `1.5 KiB`.
+pub struct Bytes(pub u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return f.pad(&format!("{} B", self.0));
+        }
+        let mut value = self.0 as f64;
+        let mut unit = "B";
+        for next in UNITS {
+            if value < 1024.0 {
+                break;
+            }
+            value /= 1024.0;
+            unit = next;
+        }
+        f.pad(&format!("{value:.1} {unit}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_display() {
+        assert_eq!(Bytes(0).to_string(), "0 B");
+        assert_eq!(Bytes(1023).to_string(), "1023 B");
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(Bytes(3 * 1024 * 1024).to_string(), "3.0 MiB");
+        assert_eq!(format!("{:>9}", Bytes(2048)), "  2.0 KiB");
+    }
+
+    #[test]
+    fn test_inactive_without_allocator() {
+        // Unit tests run on the system allocator, so nothing is counted.
+        let (vec, usage) = measure(|| vec![0_u8; 4096]);
+        assert_eq!(vec.len(), 4096);
+        assert_eq!(usage, Usage::default());
+        assert!(!is_active());
+    }
+}
@@ -0,0 +1,169 @@
+//! A small scoped thread pool on `std::thread`: workers take the next item
+//! off a shared counter until none are left, and results come back in
+//! input order whatever order they finish in. Workers share the caller's
+//! [cancellation token](crate::cancel) and [parameters](crate::params).
+//!
+//! Inside a worker the other cores are already busy, so
+//! [`available_jobs`] is one there and [`map_chunks`] runs on the worker's
+//! own thread rather than starting a pool of its own.
+
+use crate::{cancel, params};
+use std::cell::Cell;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+thread_local! {
+    static JOBS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Restores the previous limit when `with_jobs` returns or unwinds.
+struct Restore(Option<usize>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        JOBS.with(|jobs| jobs.set(self.0));
+    }
+}
+
+/// Threads to use when the caller doesn't say: one per core, or the limit
+/// of the enclosing [`with_jobs`].
+pub fn available_jobs() -> usize {
+    JOBS.with(Cell::get)
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get))
+}
+
+/// Runs `run` with [`available_jobs`] limited to `jobs`, e.g. on a thread
+/// that solves on behalf of a pool's worker.
+pub fn with_jobs<R>(jobs: usize, run: impl FnOnce() -> R) -> R {
+    let previous = JOBS.with(|current| current.replace(Some(jobs.max(1))));
+    let _restore = Restore(previous);
+    run()
+}
+
+/// Applies `f` to every item on up to `jobs` threads and returns the
+/// results in the order of `items`. A panic in `f` is re-raised.
+pub fn map<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    let token = cancel::current();
+    let current_params = params::current();
+    let work = || loop {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        let Some(item) = items.get(index) else {
+            break;
+        };
+        let result = f(item);
+        results.lock().unwrap()[index] = Some(result);
+    };
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                with_jobs(1, || {
+                    params::with_params(&current_params, || match &token {
+                        Some(token) => cancel::with_token(token, work),
+                        None => work(),
+                    })
+                })
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every item was processed"))
+        .collect()
+}
+
+/// Splits `items` into one contiguous chunk per [available
+/// job](available_jobs) and applies `f` to the chunks in parallel,
+/// returning results in chunk order.
+pub fn map_chunks<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&[T]) -> R + Sync,
+{
+    let jobs = available_jobs();
+    let chunk_size = items.len().div_ceil(jobs).max(1);
+    let chunks: Vec<&[T]> = items.chunks(chunk_size).collect();
+    map(&chunks, jobs, |chunk| f(chunk))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_keeps_input_order() {
+        let items: Vec<u64> = (0..40).collect();
+        let squares = map(&items, 8, |&item| {
+            // Early items finish last.
+            thread::sleep(Duration::from_micros(40 - item));
+            item * item
+        });
+        assert_eq!(
+            squares,
+            items.iter().map(|item| item * item).collect::<Vec<_>>()
+        );
+        assert_eq!(map(&items, 1, |item| item + 1)[39], 40);
+        assert!(map(&[] as &[u8], 4, |item| *item).is_empty());
+    }
+
+    #[test]
+    fn test_map_chunks_covers_every_item() {
+        let items: Vec<u64> = (1..=1000).collect();
+        let sums = map_chunks(&items, |chunk| chunk.iter().sum::<u64>());
+        assert_eq!(sums.iter().sum::<u64>(), 500500);
+        assert!(map_chunks(&[] as &[u64], |chunk| chunk.len()).is_empty());
+    }
+
+    #[test]
+    fn test_workers_share_token_and_params() {
+        let token = cancel::CancelToken::new();
+        token.cancel();
+        let seen = cancel::with_token(&token, || map(&[1, 2, 3, 4], 4, |_| cancel::is_cancelled()));
+        assert_eq!(seen, [true; 4]);
+
+        let current = params::Params::from([("steps".to_string(), 64)]);
+        let seen = params::with_params(&current, || map(&[1, 2], 2, |_| params::get("steps")));
+        assert_eq!(seen, [Some(64); 2]);
+    }
+
+    #[test]
+    fn test_workers_do_not_nest_pools() {
+        let items: Vec<u64> = (1..=100).collect();
+        let nested = map(&[1, 2], 2, |_| {
+            (
+                available_jobs(),
+                map_chunks(&items, |chunk| chunk.len()).len(),
+            )
+        });
+        assert_eq!(nested, [(1, 1); 2]);
+        assert_eq!(with_jobs(3, available_jobs), 3);
+        assert_eq!(
+            with_jobs(3, || map_chunks(&items, |chunk| chunk.len())).len(),
+            3
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panics_propagate() {
+        map(&[1, 2, 3], 3, |&item| assert_ne!(item, 2));
+    }
+}
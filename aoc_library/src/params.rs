@@ -0,0 +1,192 @@
+//! Named puzzle parameters, such as day 2's cube limits or a number of
+//! steps, that differ between the real input and the examples.
+//!
+//! A solution declares each one with its two defaults,
+//!
+//! ```ignore
+//! const PARAMS: &'static [Param] = &[Param::new("steps", 64, 6)];
+//! ```
+//!
+//! and the runner makes the values current for the thread solving the day:
+//! the real defaults, overridden by the day's [configuration](crate::config)
+//! and then by `--param steps=100` on the command line. Example tests get
+//! the example defaults, overridden by a `[params]` section in the
+//! example's answers file. The solution asks for a value by name:
+//!
+//! ```ignore
+//! let steps = aoc_library::params::get_or("steps", 64_usize);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+pub type Params = BTreeMap<String, i64>;
+
+/// A parameter a solution declares, with its value for the real input and
+/// for the puzzle's examples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub real: i64,
+    pub example: i64,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, real: i64, example: i64) -> Param {
+        Param {
+            name,
+            real,
+            example,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Real,
+    Example,
+}
+
+/// The declared defaults for `kind` of input.
+pub fn defaults(declared: &[Param], kind: InputKind) -> Params {
+    declared
+        .iter()
+        .map(|param| {
+            let value = match kind {
+                InputKind::Real => param.real,
+                InputKind::Example => param.example,
+            };
+            (param.name.to_string(), value)
+        })
+        .collect()
+}
+
+/// Sets each of `overrides` in `params`. A name the solution does not
+/// declare is an error, which catches typos.
+pub fn apply(declared: &[Param], params: &mut Params, overrides: &Params) -> Result<(), String> {
+    for (name, value) in overrides {
+        if !declared.iter().any(|param| param.name == name) {
+            let known: Vec<&str> = declared.iter().map(|param| param.name).collect();
+            return Err(match known.as_slice() {
+                [] => format!("unknown parameter {name}: the day declares none"),
+                known => format!(
+                    "unknown parameter {name}, expected one of {}",
+                    known.join(", ")
+                ),
+            });
+        }
+        params.insert(name.clone(), *value);
+    }
+    Ok(())
+}
+
+/// Parses `name=value` as given to `--param`.
+pub fn parse_assignment(text: &str) -> Result<(String, i64), String> {
+    let (name, value) = text
+        .split_once('=')
+        .ok_or_else(|| format!("expected name=value, got {text:?}"))?;
+    let value = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("parameter {} needs an integer, got {value:?}", name.trim()))?;
+    Ok((name.trim().to_string(), value))
+}
+
+thread_local! {
+    static CURRENT: RefCell<Params> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Restores the previous parameters when `with_params` returns or unwinds.
+struct Restore(Params);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| *current.borrow_mut() = std::mem::take(&mut self.0));
+    }
+}
+
+/// Runs `run` with `params` as this thread's parameters.
+pub fn with_params<R>(params: &Params, run: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(params.clone()));
+    let _restore = Restore(previous);
+    run()
+}
+
+/// This thread's parameters, to hand on to threads it starts.
+pub fn current() -> Params {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+pub fn get(name: &str) -> Option<i64> {
+    CURRENT.with(|current| current.borrow().get(name).copied())
+}
+
+/// The parameter `name`, or `default` when it is not set.
+///
+/// # Panics
+///
+/// When the value does not fit in `T`; a configuration mistake the
+/// solution cannot work around.
+pub fn get_or<T: TryFrom<i64>>(name: &str, default: T) -> T {
+    match get(name) {
+        None => default,
+        Some(value) => T::try_from(value).unwrap_or_else(|_| {
+            panic!(
+                "parameter {name} = {value} is out of range for {}",
+                std::any::type_name::<T>()
+            )
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_params() {
+        assert_eq!(get("red"), None);
+        let params = Params::from([("red".to_string(), 20)]);
+        with_params(&params, || {
+            assert_eq!(get("red"), Some(20));
+            assert_eq!(get_or("red", 12_u32), 20);
+            assert_eq!(get_or("blue", 14_u32), 14);
+            assert_eq!(current(), params);
+        });
+        assert_eq!(get_or("red", 12_u32), 12);
+    }
+
+    #[test]
+    fn test_defaults_and_overrides() {
+        const DECLARED: &[Param] = &[Param::new("steps", 64, 6), Param::new("size", 71, 7)];
+        let mut params = defaults(DECLARED, InputKind::Example);
+        assert_eq!(params["steps"], 6);
+
+        let size = Params::from([parse_assignment("size=100").unwrap()]);
+        apply(DECLARED, &mut params, &size).unwrap();
+        assert_eq!(
+            params,
+            Params::from([("size".to_string(), 100), ("steps".to_string(), 6)])
+        );
+
+        let typo = Params::from([("stpes".to_string(), 1)]);
+        assert_eq!(
+            apply(DECLARED, &mut params, &typo),
+            Err("unknown parameter stpes, expected one of steps, size".to_string())
+        );
+        assert!(apply(&[], &mut params, &typo)
+            .unwrap_err()
+            .contains("declares none"));
+        assert!(parse_assignment("steps").is_err());
+        assert!(parse_assignment("steps=many").is_err());
+        assert_eq!(parse_assignment("red = -3"), Ok(("red".to_string(), -3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "parameter red = -1 is out of range for u32")]
+    fn test_out_of_range_panics() {
+        with_params(&Params::from([("red".to_string(), -1)]), || {
+            get_or("red", 0_u32)
+        });
+    }
+}
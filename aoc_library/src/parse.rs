@@ -0,0 +1,478 @@
+//! Parsing puzzle lines by pattern.
+//!
+//! `#[derive(FromLine)]` reads a struct from a line shaped like its
+//! pattern:
+//!
+//! ```ignore
+//! use aoc_library::parse::FromLine;
+//!
+//! #[derive(FromLine)]
+//! #[aoc(pattern = "Game {number}: {pulls}")]
+//! struct Game {
+//!     number: u32,
+//!     #[aoc(sep = ";", sep = ",")]
+//!     pulls: Vec<Vec<Cubes>>,
+//! }
+//! ```
+//!
+//! Text in the pattern must appear in the line, except that a space
+//! matches any run of whitespace, including none. A field takes everything
+//! up to the next text of the pattern, or the next whitespace if that text
+//! is only spaces, or the rest of the line for the last field, and is then
+//! read with [`FromField`]. A `Vec` splits its text on whitespace unless
+//! the field gives separators with `sep`; each further `sep` splits the
+//! items of the list before, so the example reads
+//! `Game 1: 3 blue, 4 red; 1 red` as two pulls of cubes. A derived struct
+//! is also a field, so patterns nest.
+//!
+//! Errors point at the character where the line stopped making sense.
+
+use crate::error::Error;
+
+pub use aoc_macros::FromLine;
+
+pub trait FromLine: Sized {
+    fn from_line(line: &str) -> Result<Self, Error>;
+}
+
+/// A value read from the text a pattern's field matched, with the
+/// separators still to apply for nested lists. Error columns count from
+/// the start of `text`.
+pub trait FromField: Sized {
+    fn from_field(text: &str, separators: &[&str]) -> Result<Self, Error>;
+}
+
+/// Reads every line, numbering errors from 1.
+pub fn lines<T: FromLine>(lines: &[impl AsRef<str>]) -> Result<Vec<T>, Error> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| T::from_line(line.as_ref()).map_err(|error| error.on_line(index + 1)))
+        .collect()
+}
+
+macro_rules! from_str_fields {
+    ($($ty:ty),*) => {$(
+        impl FromField for $ty {
+            fn from_field(text: &str, _separators: &[&str]) -> Result<Self, Error> {
+                text.parse().map_err(|_| {
+                    Error::new(1, format!("expected {}, found {text:?}", stringify!($ty)))
+                })
+            }
+        }
+    )*};
+}
+
+from_str_fields!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl FromField for String {
+    fn from_field(text: &str, _separators: &[&str]) -> Result<Self, Error> {
+        Ok(text.to_string())
+    }
+}
+
+impl FromField for char {
+    fn from_field(text: &str, _separators: &[&str]) -> Result<Self, Error> {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(Error::new(
+                1,
+                format!("expected a character, found {text:?}"),
+            )),
+        }
+    }
+}
+
+impl<T: FromField> FromField for Vec<T> {
+    fn from_field(text: &str, separators: &[&str]) -> Result<Self, Error> {
+        let read = |piece: &str, inner: &[&str]| {
+            T::from_field(piece, inner).map_err(|error| error.shifted(columns_before(text, piece)))
+        };
+        match separators.split_first() {
+            None => text
+                .split_whitespace()
+                .map(|piece| read(piece, &[]))
+                .collect(),
+            Some(_) if text.trim().is_empty() => Ok(Vec::new()),
+            Some((separator, inner)) => text
+                .split(separator)
+                .map(|piece| read(piece.trim(), inner))
+                .collect(),
+        }
+    }
+}
+
+/// The number of characters of `text` before `piece`, a slice of it.
+fn columns_before(text: &str, piece: &str) -> usize {
+    let offset = piece.as_ptr() as usize - text.as_ptr() as usize;
+    text[..offset].chars().count()
+}
+
+/// Walks a line for the code `#[derive(FromLine)]` generates.
+pub struct Cursor<'a> {
+    line: &'a str,
+    /// Byte offset of the unread rest.
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(line: &'a str) -> Cursor<'a> {
+        Cursor { line, position: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.line[self.position..]
+    }
+
+    fn column(&self) -> usize {
+        self.line[..self.position].chars().count() + 1
+    }
+
+    fn error(&self, message: String) -> Error {
+        Error::new(self.column(), message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `text`, where whitespace matches any run of whitespace.
+    pub fn literal(&mut self, text: &str) -> Result<(), Error> {
+        for expected in text.chars() {
+            if expected.is_whitespace() {
+                self.skip_whitespace();
+                continue;
+            }
+            match self.rest().chars().next() {
+                Some(found) if found == expected => self.position += found.len_utf8(),
+                Some(found) => {
+                    return Err(self.error(format!("expected {text:?}, found {found:?}")))
+                }
+                None => return Err(self.error(format!("expected {text:?}, found the end"))),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a field that ends where `until`, the pattern's next text,
+    /// starts, or at the end of the line when there is none.
+    pub fn field<T: FromField>(
+        &mut self,
+        until: Option<&str>,
+        separators: &[&str],
+    ) -> Result<T, Error> {
+        let (text, start) = self.text(until)?;
+        T::from_field(text, separators).map_err(|error| error.shifted(start))
+    }
+
+    /// The trimmed text of a field, as [`field`](Cursor::field) finds it,
+    /// with the number of characters before it.
+    fn text(&mut self, until: Option<&str>) -> Result<(&'a str, usize), Error> {
+        let rest = self.rest();
+        let length = match until.map(str::trim) {
+            None => rest.len(),
+            Some("") => rest.find(char::is_whitespace).unwrap_or(rest.len()),
+            Some(next) => rest.find(next).ok_or_else(|| {
+                let end = Cursor {
+                    line: self.line,
+                    position: self.line.len(),
+                };
+                end.error(format!("expected {:?}, found the end", until.unwrap()))
+            })?,
+        };
+        let text = rest[..length].trim();
+        let start = self.column() - 1 + columns_before(rest, text);
+        self.position += length;
+        Ok((text, start))
+    }
+
+    /// Checks that nothing but whitespace is left.
+    pub fn end(&mut self) -> Result<(), Error> {
+        self.skip_whitespace();
+        match self.rest() {
+            "" => Ok(()),
+            rest => Err(self.error(format!("unexpected {rest:?}"))),
+        }
+    }
+}
+
+/// A value [`scan!`](crate::scan) can capture: anything a pattern's field
+/// can hold, or the text itself.
+pub trait Scan<'a>: Sized {
+    fn scan(text: &'a str) -> Result<Self, Error>;
+}
+
+impl<'a> Scan<'a> for &'a str {
+    fn scan(text: &'a str) -> Result<Self, Error> {
+        Ok(text)
+    }
+}
+
+impl<T: FromField> Scan<'_> for T {
+    fn scan(text: &str) -> Result<Self, Error> {
+        T::from_field(text, &[])
+    }
+}
+
+/// The number of `{}` in a [`scan!`](crate::scan) pattern, which the macro
+/// checks against its types as it expands.
+///
+/// # Panics
+///
+/// On a brace that is neither part of `{}` nor doubled, so in `scan!` it
+/// fails to compile.
+pub const fn placeholders(pattern: &str) -> usize {
+    let bytes = pattern.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let next = if i + 1 < bytes.len() { bytes[i + 1] } else { 0 };
+        match (bytes[i], next) {
+            (b'{', b'}') => count += 1,
+            (b'{', b'{') | (b'}', b'}') => {}
+            (b'{' | b'}', _) => panic!("scan!: write {{{{ and }}}} for braces in a pattern"),
+            _ => {
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+    count
+}
+
+/// Walks a line along a `scan!` pattern, one `{}` at a time.
+pub struct Scanner<'a> {
+    /// The pattern's text around its `{}`, with `{{` and `}}` undoubled.
+    texts: Vec<String>,
+    /// Texts matched so far.
+    matched: usize,
+    cursor: Cursor<'a>,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(pattern: &str, line: &'a str) -> Scanner<'a> {
+        let mut texts = vec![String::new()];
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('{', Some('}')) => {
+                    chars.next();
+                    texts.push(String::new());
+                    continue;
+                }
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    chars.next();
+                }
+                _ => {}
+            }
+            texts.last_mut().unwrap().push(c);
+        }
+        Scanner {
+            texts,
+            matched: 0,
+            cursor: Cursor::new(line),
+        }
+    }
+
+    /// Matches the pattern's text up to the next `{}` and reads the capture
+    /// after it.
+    ///
+    /// # Panics
+    ///
+    /// When the pattern has no `{}` left, which `scan!` rules out as it
+    /// expands.
+    pub fn capture<T: Scan<'a>>(&mut self) -> Result<T, Error> {
+        assert!(
+            self.matched + 1 < self.texts.len(),
+            "scan!: more types than {{}} in the pattern"
+        );
+        self.cursor.literal(&self.texts[self.matched])?;
+        self.matched += 1;
+        let until = Some(self.texts[self.matched].as_str()).filter(|until| !until.is_empty());
+        let (text, start) = self.cursor.text(until)?;
+        T::scan(text).map_err(|error| error.shifted(start))
+    }
+
+    /// Matches the pattern's remaining text and checks that the line ends.
+    pub fn end(&mut self) -> Result<(), Error> {
+        if let Some(rest) = self.texts.get(self.matched) {
+            self.cursor.literal(rest)?;
+        }
+        self.cursor.end()
+    }
+}
+
+/// Pulls values out of a line with a format-style pattern, for lines not
+/// worth a [`FromLine`] struct:
+///
+/// ```
+/// use aoc_library::scan;
+///
+/// let (game, pulls) = scan!("Game {}: {}", "Game 22: 3 red" => u32, &str)?;
+/// assert_eq!((game, pulls), (22, "3 red"));
+/// # Ok::<(), aoc_library::Error>(())
+/// ```
+///
+/// Each `{}` takes one of the listed types: an integer, a `&str` or
+/// `String`, or anything else with [`FromField`]. A capture ends where the
+/// pattern's next text starts, at the next whitespace if that text is only
+/// spaces, and at the end of the line for the last `{}`. As with
+/// `#[derive(FromLine)]`, a space in the pattern matches any run of
+/// whitespace, and `{{` and `}}` stand for braces. The result is a tuple
+/// of the captures, or the [`Error`] at the first character that did not
+/// fit.
+///
+/// The pattern is a string literal with exactly one `{}` per type, which
+/// is checked as the macro expands:
+///
+/// ```compile_fail
+/// let _ = aoc_library::scan!("Game {}", "Game 1" => u32, u32);
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($pattern:literal, $line:expr => $($ty:ty),+ $(,)?) => {{
+        const _: () = ::core::assert!(
+            $crate::parse::placeholders($pattern) == [$(::core::stringify!($ty)),+].len(),
+            "scan!: the pattern needs one {{}} per type"
+        );
+        let mut scanner = $crate::parse::Scanner::new($pattern, $line);
+        (|| -> ::core::result::Result<($($ty,)+), $crate::Error> {
+            let values = ($(scanner.capture::<$ty>()?,)+);
+            scanner.end()?;
+            ::core::result::Result::Ok(values)
+        })()
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, FromLine)]
+    #[aoc(pattern = "Card {id}: {numbers} | {winning}")]
+    struct Card {
+        id: u32,
+        numbers: Vec<u32>,
+        winning: Vec<u8>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, FromLine)]
+    #[aoc(pattern = "{count} {color}")]
+    struct Cubes {
+        count: u32,
+        color: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, FromLine)]
+    #[aoc(pattern = "Game {number}: {pulls}")]
+    struct Game {
+        number: u32,
+        #[aoc(sep = ";", sep = ",")]
+        pulls: Vec<Vec<Cubes>>,
+    }
+
+    #[test]
+    fn test_derived_struct() {
+        let card = Card::from_line("Card   1: 41 48 | 83  6 9").unwrap();
+        assert_eq!(
+            card,
+            Card {
+                id: 1,
+                numbers: vec![41, 48],
+                winning: vec![83, 6, 9],
+            }
+        );
+    }
+
+    #[test]
+    fn test_nested_separators() {
+        let game = Game::from_line("Game 2: 3 blue, 4 red; 1 red").unwrap();
+        assert_eq!(game.number, 2);
+        assert_eq!(game.pulls.len(), 2);
+        assert_eq!(game.pulls[0][1].count, 4);
+        assert_eq!(game.pulls[1][0].color, "red");
+    }
+
+    #[test]
+    fn test_error_columns() {
+        let error = Card::from_line("Card 1: 41 4x | 83").unwrap_err();
+        assert_eq!(error.column, 12);
+        assert_eq!(error.message, "expected u32, found \"4x\"");
+
+        let error = Card::from_line("Card 1: 41 | 83 300").unwrap_err();
+        assert_eq!(error.column, 17);
+
+        let error = Card::from_line("Crd 1: 41 | 83").unwrap_err();
+        assert_eq!(error.to_string(), "column 2: expected \"Card \", found 'r'");
+
+        let error = Card::from_line("Card 1: 41 48").unwrap_err();
+        assert_eq!(error.column, 14);
+
+        let error = Game::from_line("Game 2: 3 blue; x red").unwrap_err();
+        assert_eq!(error.column, 17);
+    }
+
+    #[test]
+    fn test_scan_captures() {
+        assert_eq!(scan!("Game {}", "Game 22" => u32), Ok((22,)));
+        assert_eq!(
+            scan!("{} -> {}", "abc -> x, y z" => &str, String),
+            Ok(("abc", "x, y z".to_string()))
+        );
+        assert_eq!(
+            scan!("move {} from {} to {}", "move  3 from -1 to 9" => u8, i32, usize),
+            Ok((3, -1, 9))
+        );
+        assert_eq!(
+            scan!("{} {}", "pos=1 rest" => &str, &str),
+            Ok(("pos=1", "rest"))
+        );
+    }
+
+    #[test]
+    fn test_scan_errors() {
+        let error = scan!("Game {}: {}", "Game x: 1" => u32, &str).unwrap_err();
+        assert_eq!(error.to_string(), "column 6: expected u32, found \"x\"");
+
+        let error = scan!("Game {}: {}", "Game 1 2" => u32, &str).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "column 9: expected \": \", found the end"
+        );
+
+        let error = scan!("{} ok", "1 not" => u32).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "column 6: expected \" ok\", found the end"
+        );
+    }
+
+    #[test]
+    fn test_scan_braces() {
+        assert_eq!(scan!("{{{}}}", "{7}" => u32), Ok((7,)));
+        assert_eq!(scan!("{{{}, {}}}", "{x, -2}" => char, i8), Ok(('x', -2)));
+        assert_eq!(placeholders("{} {{}} {}"), 2);
+        assert_eq!(placeholders("no captures"), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "for braces")]
+    fn test_placeholders_rejects_single_braces() {
+        placeholders("{x}");
+    }
+
+    #[test]
+    fn test_lines_numbers_errors() {
+        let cards: Vec<Card> = lines(&["Card 1: 1 | 2", "Card 2: 3 | 4"]).unwrap();
+        assert_eq!(cards[1].numbers, [3]);
+
+        let error = lines::<Card>(&["Card 1: 1 | 2", "Card 2: 3 | 4 |"]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 15: expected u8, found \"|\""
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Fetching puzzle descriptions and turning their example blocks into
+//! example files.
+//!
+//! Pages are cached as `<cache>/<year>/day_<day>.html`; a cached page is
+//! only fetched again when asked to refresh, e.g. after solving part one
+//! unlocks part two.
+
+use crate::html;
+use crate::http::HttpBackend;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the adventofcode.com session cookie value.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+pub fn puzzle_url(year: u16, day: u8) -> String {
+    format!("{BASE_URL}/{year}/day/{day}")
+}
+
+/// The session token from `AOC_SESSION`, or else from the first line of
+/// `session_file`. Without one only part one of a puzzle is visible.
+pub fn session_token(session_file: &Path) -> Option<String> {
+    let token = std::env::var(SESSION_VAR)
+        .ok()
+        .or_else(|| fs::read_to_string(session_file).ok())?;
+    let token = token.lines().next()?.trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+pub struct Fetcher<'a> {
+    pub backend: &'a dyn HttpBackend,
+    pub cache_dir: PathBuf,
+    pub session: Option<String>,
+}
+
+impl Fetcher<'_> {
+    pub fn cache_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day_{day}.html"))
+    }
+
+    /// The puzzle page, from the cache unless `refresh` is set or it was
+    /// never fetched.
+    pub fn puzzle_html(&self, year: u16, day: u8, refresh: bool) -> Result<String, String> {
+        let path = self.cache_path(year, day);
+        if !refresh {
+            if let Ok(html) = fs::read_to_string(&path) {
+                return Ok(html);
+            }
+        }
+
+        let cookie = self
+            .session
+            .as_ref()
+            .map(|token| format!("session={token}"));
+        let html = self
+            .backend
+            .get(&puzzle_url(year, day), cookie.as_deref())?;
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| fs::write(&path, &html))
+            .map_err(|error| format!("{}: {error}", path.display()))?;
+        Ok(html)
+    }
+}
+
+/// Writes each example block not already present in `dir` as
+/// `puzzle_<n>.txt`, numbered in page order, next to a `puzzle_<n>.toml`
+/// whose answers are left for filling in. Returns the new input files.
+pub fn write_examples(dir: &Path, html: &str) -> Result<Vec<PathBuf>, String> {
+    let io_error = |path: &Path, error: std::io::Error| format!("{}: {error}", path.display());
+    fs::create_dir_all(dir).map_err(|error| io_error(dir, error))?;
+
+    let existing: Vec<String> = fs::read_dir(dir)
+        .map_err(|error| io_error(dir, error))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .collect();
+
+    let mut written = Vec::new();
+    for (number, block) in (1..).zip(html::code_blocks(html)) {
+        if existing.contains(&block) {
+            continue;
+        }
+        let input = dir.join(format!("puzzle_{number}.txt"));
+        if input.exists() {
+            continue;
+        }
+        let answers = input.with_extension("toml");
+        fs::write(&input, &block).map_err(|error| io_error(&input, error))?;
+        fs::write(
+            &answers,
+            format!(
+                "# Expected answers for {}, from the puzzle text.\n# part_one = 0\n# part_two = 0\n",
+                input.file_name().unwrap().to_string_lossy()
+            ),
+        )
+        .map_err(|error| io_error(&answers, error))?;
+        written.push(input);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::load_examples;
+    use crate::http::LocalBackend;
+    use crate::tests::scratch_dir;
+
+    const PAGE: &str = "<article><h2>Day 4</h2><p>For example:</p>\
+        <pre><code>Card 1: 41 48 | 83 86\n</code></pre>\
+        <p>and</p><pre><code>Card 2: 1 | 1\n</code></pre></article>";
+
+    #[test]
+    fn test_fetch_is_cached() {
+        let dir = scratch_dir("puzzle-cache");
+        let mut backend = LocalBackend::default();
+        backend.insert("https://adventofcode.com/2023/day/4", PAGE);
+        let fetcher = Fetcher {
+            backend: &backend,
+            cache_dir: dir.clone(),
+            session: Some("token".to_string()),
+        };
+
+        assert_eq!(fetcher.puzzle_html(2023, 4, false).unwrap(), PAGE);
+        assert_eq!(fetcher.puzzle_html(2023, 4, false).unwrap(), PAGE);
+        assert_eq!(backend.requests().len(), 1);
+        assert!(dir.join("2023/day_4.html").exists());
+
+        fetcher.puzzle_html(2023, 4, true).unwrap();
+        assert_eq!(backend.requests().len(), 2);
+        assert!(fetcher.puzzle_html(2023, 5, false).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_examples_skips_known_blocks() {
+        let dir = scratch_dir("puzzle-examples");
+        fs::write(dir.join("mine.txt"), "Card 2: 1 | 1\n").unwrap();
+        fs::write(dir.join("mine.toml"), "part_one = 1\n").unwrap();
+
+        let written = write_examples(&dir, PAGE).unwrap();
+        assert_eq!(written, [dir.join("puzzle_1.txt")]);
+        assert_eq!(
+            fs::read_to_string(&written[0]).unwrap(),
+            "Card 1: 41 48 | 83 86\n"
+        );
+        assert!(write_examples(&dir, PAGE).unwrap().is_empty());
+
+        // The generated answers file is valid, just empty.
+        let examples = load_examples(&dir).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[1].name, "puzzle_1");
+        assert_eq!(examples[1].part_one, None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,265 @@
+//! A progress overview of every registered day, as a markdown table for a
+//! README or a self-contained HTML page.
+//!
+//! Stars come from the answer ledger: a part with an answer recorded in
+//! `answers.toml` has been solved. Runtimes are the latest benchmark
+//! medians for this machine, and source links are relative to the
+//! workspace root.
+
+use crate::history::{self, Record};
+use crate::solution::{Answers, Day, Stage};
+use crate::verify;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayEntry {
+    pub year: u16,
+    pub day: u8,
+    /// The day's `src/lib.rs`, relative to the workspace root.
+    pub source: String,
+    /// Recorded answers; each one is a star.
+    pub answers: Answers,
+    pub part_one_time: Option<Duration>,
+    pub part_two_time: Option<Duration>,
+}
+
+impl DayEntry {
+    pub fn stars(&self) -> usize {
+        [self.answers.part_one, self.answers.part_two]
+            .iter()
+            .filter(|answer| answer.is_some())
+            .count()
+    }
+
+    /// Answer and runtime of part one and part two. An unsolved part's
+    /// runtime is meaningless, so it is left out.
+    fn parts(&self) -> [(Option<u64>, Option<Duration>); 2] {
+        [
+            (self.answers.part_one, self.part_one_time),
+            (self.answers.part_two, self.part_two_time),
+        ]
+        .map(|(answer, time)| (answer, time.filter(|_| answer.is_some())))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub entries: Vec<DayEntry>,
+    /// The machine whose benchmarks supplied the runtimes.
+    pub machine: String,
+}
+
+impl Report {
+    /// Collects the entries for `days` from their answer ledgers and the
+    /// benchmark `history` recorded on `machine`.
+    pub fn build(
+        days: &[&Day],
+        workspace: &Path,
+        history: &[Record],
+        machine: &str,
+    ) -> Result<Report, String> {
+        let mut entries = Vec::new();
+        for day in days {
+            let dir = Path::new(day.dir);
+            let source = dir
+                .strip_prefix(workspace)
+                .unwrap_or(dir)
+                .join("src")
+                .join("lib.rs");
+            let time = |stage| {
+                history::latest(history, machine, day.year, day.day, stage)
+                    .map(|record| record.stats.median)
+            };
+            entries.push(DayEntry {
+                year: day.year,
+                day: day.day,
+                source: source.to_string_lossy().replace('\\', "/"),
+                answers: verify::expected_answers(day)?.unwrap_or_default(),
+                part_one_time: time(Stage::PartOne),
+                part_two_time: time(Stage::PartTwo),
+            });
+        }
+        entries.sort_by_key(|entry| (entry.year, entry.day));
+        Ok(Report {
+            entries,
+            machine: machine.to_string(),
+        })
+    }
+
+    fn years(&self) -> BTreeMap<u16, Vec<&DayEntry>> {
+        let mut years: BTreeMap<u16, Vec<&DayEntry>> = BTreeMap::new();
+        for entry in &self.entries {
+            years.entry(entry.year).or_default().push(entry);
+        }
+        years
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut markdown = String::from("# Advent of Code progress\n");
+        for (year, entries) in self.years() {
+            let stars: usize = entries.iter().map(|entry| entry.stars()).sum();
+            writeln!(markdown, "\n## {year}: {stars} of 50 stars\n").unwrap();
+            markdown.push_str("| Day | Stars | Part one | Time | Part two | Time | Source |\n");
+            markdown.push_str("|----:|:------|---------:|-----:|---------:|-----:|:-------|\n");
+            for entry in entries {
+                write!(markdown, "| {} | {} |", entry.day, star_string(entry)).unwrap();
+                for (answer, time) in entry.parts() {
+                    write!(markdown, " {} | {} |", show(answer), show_time(time)).unwrap();
+                }
+                writeln!(markdown, " [{0}]({0}) |", entry.source).unwrap();
+            }
+        }
+        writeln!(
+            markdown,
+            "\nTimes are benchmark medians on `{}`.",
+            self.machine
+        )
+        .unwrap();
+        markdown
+    }
+
+    pub fn html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Advent of Code progress</title>\n<style>\n\
+             body { font-family: monospace; background: #0f0f23; color: #cccccc; margin: 2em; }\n\
+             h1, h2 { color: #00cc00; }\n\
+             table { border-collapse: collapse; margin-bottom: 2em; }\n\
+             th, td { padding: 0.2em 1em; border-bottom: 1px solid #333340; }\n\
+             td.number { text-align: right; }\n\
+             .stars { color: #ffff66; }\n\
+             a { color: #009900; }\n\
+             </style>\n</head>\n<body>\n<h1>Advent of Code progress</h1>\n",
+        );
+        for (year, entries) in self.years() {
+            let stars: usize = entries.iter().map(|entry| entry.stars()).sum();
+            writeln!(
+                html,
+                "<h2>{year}: <span class=\"stars\">{stars}</span> of 50 stars</h2>\n<table>\n\
+                 <tr><th>Day</th><th>Stars</th><th>Part one</th><th>Time</th>\
+                 <th>Part two</th><th>Time</th><th>Source</th></tr>"
+            )
+            .unwrap();
+            for entry in entries {
+                write!(
+                    html,
+                    "<tr><td class=\"number\">{}</td><td class=\"stars\">{}</td>",
+                    entry.day,
+                    star_string(entry)
+                )
+                .unwrap();
+                for (answer, time) in entry.parts() {
+                    write!(
+                        html,
+                        "<td class=\"number\">{}</td><td class=\"number\">{}</td>",
+                        show(answer),
+                        escape(&show_time(time))
+                    )
+                    .unwrap();
+                }
+                writeln!(
+                    html,
+                    "<td><a href=\"{0}\">{0}</a></td></tr>",
+                    escape(&entry.source)
+                )
+                .unwrap();
+            }
+            html.push_str("</table>\n");
+        }
+        writeln!(
+            html,
+            "<p>Times are benchmark medians on <code>{}</code>.</p>\n</body>\n</html>",
+            escape(&self.machine)
+        )
+        .unwrap();
+        html
+    }
+}
+
+fn star_string(entry: &DayEntry) -> String {
+    "★".repeat(entry.stars())
+}
+
+fn show(answer: Option<u64>) -> String {
+    answer.map_or("-".to_string(), |answer| answer.to_string())
+}
+
+fn show_time(time: Option<Duration>) -> String {
+    time.map_or("-".to_string(), |time| format!("{time:.2?}"))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> Report {
+        Report {
+            entries: vec![
+                DayEntry {
+                    year: 2023,
+                    day: 1,
+                    source: "2023/day_1/src/lib.rs".to_string(),
+                    answers: Answers {
+                        part_one: Some(54390),
+                        part_two: Some(54277),
+                    },
+                    part_one_time: Some(Duration::from_micros(977)),
+                    part_two_time: None,
+                },
+                DayEntry {
+                    year: 2023,
+                    day: 4,
+                    source: "2023/day_4/src/lib.rs".to_string(),
+                    answers: Answers {
+                        part_one: Some(24848),
+                        part_two: None,
+                    },
+                    part_one_time: None,
+                    part_two_time: Some(Duration::from_nanos(60)),
+                },
+            ],
+            machine: "host-linux-x86_64".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = report().markdown();
+        assert!(markdown.contains("## 2023: 3 of 50 stars"));
+        assert!(markdown.contains(
+            "| 1 | ★★ | 54390 | 977.00µs | 54277 | - | [2023/day_1/src/lib.rs](2023/day_1/src/lib.rs) |"
+        ));
+        assert!(markdown.contains("| 4 | ★ | 24848 | - | - | - |"));
+        assert!(markdown.ends_with("medians on `host-linux-x86_64`.\n"));
+    }
+
+    #[test]
+    fn test_html_is_self_contained() {
+        let html = report().html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(!html.contains("<link") && !html.contains("<script"));
+        assert!(html.contains("<a href=\"2023/day_4/src/lib.rs\">"));
+        assert_eq!(html.matches("<tr><td").count(), 2);
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_build_uses_ledger_and_history() {
+        let day = crate::solution::tests::FIXTURE;
+        let report = Report::build(&[&day], Path::new("/nowhere"), &[], "m").unwrap();
+        assert_eq!(report.entries[0].source, "fixture/src/lib.rs");
+        assert_eq!(report.entries[0].stars(), 0);
+        assert_eq!(report.entries[0].part_one_time, None);
+    }
+}
@@ -0,0 +1,736 @@
+//! Running a day on its input and reporting the result: a table for people,
+//! JSON for tools. Shared by the runner and the day binaries.
+//!
+//! A day's JSON object looks like
+//!
+//! ```text
+//! {"year":2023,"day":4,"cached":false,"parse":{"time_ns":2630000,"memory":null},
+//!  "parts":[{"part":1,"answer":24848,"time_ns":589790,"memory":null,
+//!            "status":"pass","expected":24848}, ...]}
+//! ```
+//!
+//! where `status` is `pass`, `fail`, `unverified`, `unsolved` or
+//! `timeout`, and `memory` holds `allocations`, `bytes` and `peak` when a
+//! counting allocator is installed. A day that could not be run at all, for
+//! a missing input, bad settings or a panic, is a [`DayError`] instead:
+//! `{"year":2023,"day":4,"error":"..."}`.
+//!
+//! With a time limit the day is solved on a thread of its own and each
+//! stage gets its own [cancellation token](crate::cancel), cancelled once
+//! the stage runs past the limit. The stage is reported as timed out and
+//! its answer dropped. If it stops within [`CANCEL_GRACE`] the day carries
+//! on with the next stage; otherwise the thread is left behind and the
+//! remaining stages are reported as timed out too. So are the parts after
+//! a cancelled parse, which are skipped rather than handed whatever input
+//! the parse had got to.
+
+use crate::cancel::{self, CancelToken};
+use crate::config::{Config, Verbosity};
+use crate::json::Json;
+use crate::memory::{self, Bytes, MemoryMeter, Usage};
+use crate::parallel;
+use crate::params::{self, Params};
+use crate::solution::{Answers, Day, Meter, Stage, Timings};
+use crate::verify::{self, Verdict};
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a cancelled stage has to stop before it is abandoned.
+pub const CANCEL_GRACE: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub year: u16,
+    pub day: u8,
+    pub answers: Answers,
+    pub timings: Timings,
+    /// Heap usage per stage, when a counting allocator is installed.
+    pub memory: Option<[Usage; 3]>,
+    /// Part one and part two checked against the day's `answers.toml`.
+    pub verdicts: [Verdict; 2],
+    /// Stages that ran past the time limit, indexed by stage.
+    pub timed_out: [bool; 3],
+    /// Answers and timings come from an earlier run; see [`crate::cache`].
+    pub cached: bool,
+}
+
+/// Solves `day` on `lines` with `params` current, timing each stage and
+/// checking the answers. With a `limit` every stage is cut short after
+/// that long. A panicking solution is an error.
+pub fn run(
+    day: &Day,
+    lines: &[String],
+    params: &Params,
+    limit: Option<Duration>,
+) -> Result<DayRun, String> {
+    let expected = verify::expected_answers(day)?.unwrap_or_default();
+    let solved = match limit {
+        None => panic::catch_unwind(AssertUnwindSafe(|| {
+            params::with_params(params, || solve(day, lines))
+        }))
+        .map_err(|payload| panicked(&*payload))?,
+        Some(limit) => solve_within(day, lines, params, limit)?,
+    };
+
+    Ok(DayRun {
+        year: day.year,
+        day: day.day,
+        answers: solved.answers,
+        timings: solved.timings,
+        memory: solved.usage.filter(|_| memory::is_active()),
+        verdicts: verify::verify(&expected, &solved.answers),
+        timed_out: solved.timed_out,
+        cached: false,
+    })
+}
+
+#[derive(Default)]
+struct Solved {
+    answers: Answers,
+    timings: Timings,
+    /// Missing when the day was abandoned part way through.
+    usage: Option<[Usage; 3]>,
+    timed_out: [bool; 3],
+}
+
+fn solve(day: &Day, lines: &[String]) -> Solved {
+    let mut timings = Timings::default();
+    let mut meter = MemoryMeter::new(&mut timings);
+    let answers = day.solve(lines, &mut meter);
+    let usage = Stage::ALL.map(|stage| meter.get(stage));
+    Solved {
+        answers,
+        timings,
+        usage: Some(usage),
+        timed_out: [false; 3],
+    }
+}
+
+/// What the solving thread reports as it goes.
+enum Event {
+    Started(Stage, CancelToken),
+    Finished(Stage, Duration),
+    Answered(Stage, Option<u64>),
+    Done(Box<Solved>),
+}
+
+/// Runs each stage under a fresh token and reports its progress.
+struct Watchdog<'a> {
+    inner: &'a mut dyn Meter,
+    events: Sender<Event>,
+    /// Set once the parse is cancelled, leaving nothing to solve.
+    skip_parts: bool,
+}
+
+impl Meter for Watchdog<'_> {
+    fn measure(&mut self, stage: Stage, run: &mut dyn FnMut()) {
+        if self.skip_parts {
+            return;
+        }
+        let token = CancelToken::new();
+        // The receiver is gone once the day is abandoned; keep going
+        // quietly until the stage gives up.
+        let _ = self.events.send(Event::Started(stage, token.clone()));
+        let start = Instant::now();
+        cancel::with_token(&token, || self.inner.measure(stage, run));
+        let _ = self.events.send(Event::Finished(stage, start.elapsed()));
+        self.skip_parts = stage == Stage::Parse && token.is_cancelled();
+    }
+
+    fn answer(&mut self, stage: Stage, answer: Option<u64>) {
+        let _ = self.events.send(Event::Answered(stage, answer));
+    }
+}
+
+fn solve_within(
+    day: &Day,
+    lines: &[String],
+    params: &Params,
+    limit: Duration,
+) -> Result<Solved, String> {
+    let (events, received) = mpsc::channel();
+    let worker = {
+        let day = *day;
+        let lines = lines.to_vec();
+        let params = params.clone();
+        // Solving on behalf of this thread, so under the same limit.
+        let jobs = parallel::available_jobs();
+        thread::spawn(move || {
+            let mut timings = Timings::default();
+            let mut meter = MemoryMeter::new(&mut timings);
+            let answers = parallel::with_jobs(jobs, || {
+                params::with_params(&params, || {
+                    day.solve(
+                        &lines,
+                        &mut Watchdog {
+                            inner: &mut meter,
+                            events: events.clone(),
+                            skip_parts: false,
+                        },
+                    )
+                })
+            });
+            let usage = Stage::ALL.map(|stage| meter.get(stage));
+            let _ = events.send(Event::Done(Box::new(Solved {
+                answers,
+                timings,
+                usage: Some(usage),
+                timed_out: [false; 3],
+            })));
+        })
+    };
+
+    let mut solved = Solved::default();
+    // The running stage, its token, and when to cancel or abandon it.
+    let mut running: Option<(Stage, CancelToken, Instant)> = None;
+    loop {
+        let event = match &running {
+            Some((_, _, deadline)) => {
+                received.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => received.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match event {
+            Ok(Event::Started(stage, token)) => {
+                running = Some((stage, token, Instant::now() + limit));
+            }
+            Ok(Event::Finished(stage, elapsed)) => {
+                set_time(&mut solved.timings, stage, elapsed);
+                running = None;
+            }
+            Ok(Event::Answered(stage, answer)) => {
+                if !solved.timed_out[stage as usize] {
+                    set_answer(&mut solved.answers, stage, answer);
+                }
+            }
+            Ok(Event::Done(done)) => {
+                solved.timings = done.timings;
+                solved.usage = done.usage;
+                break;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let (stage, token, _) = running.take().expect("waiting on a stage");
+                if token.is_cancelled() {
+                    // Past the grace period as well: leave it behind, and
+                    // the stages after it unrun.
+                    solved.timed_out[stage as usize..].fill(true);
+                    break;
+                }
+                token.cancel();
+                match stage {
+                    // The worker skips the parts once the parse is cancelled.
+                    Stage::Parse => solved.timed_out.fill(true),
+                    _ => solved.timed_out[stage as usize] = true,
+                }
+                set_time(&mut solved.timings, stage, limit);
+                running = Some((stage, token, Instant::now() + CANCEL_GRACE));
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                // The solution panicked.
+                if let Err(payload) = worker.join() {
+                    return Err(panicked(&*payload));
+                }
+                break;
+            }
+        }
+    }
+    Ok(solved)
+}
+
+/// The error for a solution that panicked with `payload`.
+fn panicked(payload: &(dyn Any + Send)) -> String {
+    let message = match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => message,
+        (_, Some(message)) => message.as_str(),
+        (None, None) => "no message",
+    };
+    format!("solution panicked: {message}")
+}
+
+fn set_time(timings: &mut Timings, stage: Stage, time: Duration) {
+    match stage {
+        Stage::Parse => timings.parse = time,
+        Stage::PartOne => timings.part_one = time,
+        Stage::PartTwo => timings.part_two = time,
+    }
+}
+
+fn set_answer(answers: &mut Answers, stage: Stage, answer: Option<u64>) {
+    match stage {
+        Stage::Parse => {}
+        Stage::PartOne => answers.part_one = answer,
+        Stage::PartTwo => answers.part_two = answer,
+    }
+}
+
+fn usage_json(usage: Option<Usage>) -> Json {
+    usage.map_or(Json::Null, |usage| {
+        Json::object([
+            ("allocations", usage.allocations.into()),
+            ("bytes", usage.bytes.into()),
+            ("peak", usage.peak.into()),
+        ])
+    })
+}
+
+fn nanos(duration: std::time::Duration) -> Json {
+    Json::Integer(duration.as_nanos() as i128)
+}
+
+impl DayRun {
+    pub fn answer(&self, stage: Stage) -> Option<u64> {
+        match stage {
+            Stage::Parse => None,
+            Stage::PartOne => self.answers.part_one,
+            Stage::PartTwo => self.answers.part_two,
+        }
+    }
+
+    pub fn verdict(&self, stage: Stage) -> Option<Verdict> {
+        match stage {
+            Stage::Parse => None,
+            Stage::PartOne => Some(self.verdicts[0]),
+            Stage::PartTwo => Some(self.verdicts[1]),
+        }
+    }
+
+    pub fn timed_out(&self, stage: Stage) -> bool {
+        self.timed_out[stage as usize]
+    }
+
+    /// The part's verdict status, or `timeout` when it ran out of time.
+    pub fn status(&self, stage: Stage) -> &'static str {
+        match self.verdict(stage) {
+            _ if self.timed_out(stage) => "timeout",
+            Some(verdict) => verdict.status(),
+            None => "",
+        }
+    }
+
+    /// The day and its answers on one line, for quiet output.
+    pub fn brief(&self) -> String {
+        let parts = [Stage::PartOne, Stage::PartTwo].map(|stage| match self.answer(stage) {
+            _ if self.timed_out(stage) => "TIMEOUT".to_string(),
+            None => "-".to_string(),
+            Some(answer) => answer.to_string(),
+        });
+        let failed = [Stage::PartOne, Stage::PartTwo]
+            .into_iter()
+            .any(|stage| self.status(stage) == "fail");
+        format!(
+            "{} day {:<4}{:>16}{:>16}{}",
+            self.year,
+            self.day,
+            parts[0],
+            parts[1],
+            if failed { "  FAIL" } else { "" }
+        )
+    }
+
+    fn usage(&self, stage: Stage) -> Option<Usage> {
+        self.memory.map(|usage| usage[stage as usize])
+    }
+
+    pub fn to_json(&self) -> Json {
+        let parts = [(1_u8, Stage::PartOne), (2, Stage::PartTwo)].map(|(part, stage)| {
+            let verdict = self.verdict(stage).unwrap_or(Verdict::Unsolved);
+            let expected = match verdict {
+                Verdict::Pass => self.answer(stage),
+                Verdict::Fail { expected, .. } => Some(expected),
+                Verdict::Unverified(_) | Verdict::Unsolved => None,
+            };
+            Json::object([
+                ("part", part.into()),
+                ("answer", self.answer(stage).into()),
+                ("time_ns", nanos(self.timings.get(stage))),
+                ("memory", usage_json(self.usage(stage))),
+                ("status", self.status(stage).into()),
+                ("expected", expected.into()),
+            ])
+        });
+
+        Json::object([
+            ("year", self.year.into()),
+            ("day", self.day.into()),
+            ("cached", self.cached.into()),
+            (
+                "parse",
+                Json::object([
+                    ("time_ns", nanos(self.timings.parse)),
+                    ("memory", usage_json(self.usage(Stage::Parse))),
+                ]),
+            ),
+            ("parts", Json::Array(parts.to_vec())),
+        ])
+    }
+}
+
+/// A day that could not be run, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayError {
+    pub year: u16,
+    pub day: u8,
+    pub message: String,
+}
+
+impl DayError {
+    pub fn new(day: &Day, message: String) -> DayError {
+        DayError {
+            year: day.year,
+            day: day.day,
+            message,
+        }
+    }
+
+    /// The day and the error on one line, for quiet output.
+    pub fn brief(&self) -> String {
+        format!("{} day {:<4}  ERROR {}", self.year, self.day, self.message)
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("year", self.year.into()),
+            ("day", self.day.into()),
+            ("error", self.message.as_str().into()),
+        ])
+    }
+}
+
+impl fmt::Display for DayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} day {}", self.year, self.day)?;
+        writeln!(f, "  ERROR {}", self.message)
+    }
+}
+
+/// Every run plus the days that could not be run and totals, for
+/// reporting several days at once.
+pub fn summary_json(runs: &[DayRun], errors: &[DayError]) -> Json {
+    let count = |status: &str| {
+        runs.iter()
+            .flat_map(|run| [Stage::PartOne, Stage::PartTwo].map(|stage| run.status(stage)))
+            .filter(|part| *part == status)
+            .count()
+    };
+    let total_time = runs.iter().map(|run| run.timings.total()).sum();
+
+    Json::object([
+        (
+            "days",
+            Json::Array(runs.iter().map(DayRun::to_json).collect()),
+        ),
+        (
+            "errors",
+            Json::Array(errors.iter().map(DayError::to_json).collect()),
+        ),
+        (
+            "summary",
+            Json::object([
+                ("days", (runs.len() as u64).into()),
+                ("time_ns", nanos(total_time)),
+                ("passed", (count("pass") as u64).into()),
+                ("failed", (count("fail") as u64).into()),
+                ("unverified", (count("unverified") as u64).into()),
+                ("unsolved", (count("unsolved") as u64).into()),
+                ("timed_out", (count("timeout") as u64).into()),
+                ("errors", (errors.len() as u64).into()),
+            ]),
+        ),
+    ])
+}
+
+impl fmt::Display for DayRun {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)?;
+        match self.cached {
+            true => writeln!(f, " (cached)")?,
+            false => writeln!(f)?,
+        }
+        for stage in Stage::ALL {
+            let answer = match (stage, self.answer(stage)) {
+                _ if self.timed_out(stage) => "TIMEOUT".to_string(),
+                (Stage::Parse, _) => String::new(),
+                (_, None) => "-".to_string(),
+                (_, Some(answer)) => answer.to_string(),
+            };
+            write!(
+                f,
+                "  {stage:<10}{answer:>16}{:>12.2?}",
+                self.timings.get(stage)
+            )?;
+            if let Some(usage) = self.usage(stage) {
+                write!(
+                    f,
+                    "{:>10} allocs{:>12} total{:>12} peak",
+                    usage.allocations,
+                    Bytes(usage.bytes),
+                    Bytes(usage.peak)
+                )?;
+            }
+            match self.verdict(stage) {
+                _ if self.timed_out(stage) => writeln!(f)?,
+                Some(verdict @ (Verdict::Pass | Verdict::Fail { .. })) => {
+                    writeln!(f, "  {verdict}")?
+                }
+                _ => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Entry point for a day crate's binary: solves the day's input with the
+/// parameters from the nearest [`aoc.toml`](crate::config) and any given
+/// as `--param name=value`, and prints the result at the configured
+/// verbosity, or as JSON when run with `--json`.
+pub fn main(day: &Day) -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    let (json, overrides) = match parse_main_args(&args[1..]) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{message}");
+            eprintln!("usage: {} [--json] [--param NAME=VALUE]...", args[0]);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = Config::discover(Path::new(day.dir)).and_then(|config| {
+        let mut settings = config.day(day)?;
+        params::apply(day.params, &mut settings.params, &overrides)?;
+        let lines = crate::read_file_to_vec(&settings.input.to_string_lossy())
+            .map_err(|error| format!("{}: {error}", settings.input.display()))?;
+        let run = run(day, &lines, &settings.params, None)?;
+        Ok((run, config.verbosity))
+    });
+    match result {
+        Ok((run, _)) if json => println!("{}", run.to_json()),
+        Ok((run, Verbosity::Quiet)) => println!("{}", run.brief()),
+        Ok((run, _)) => print!("{run}"),
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn parse_main_args(args: &[String]) -> Result<(bool, Params), String> {
+    let mut json = false;
+    let mut overrides = Params::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--param" => {
+                let assignment = args.next().ok_or("--param needs name=value")?;
+                let (name, value) = params::parse_assignment(assignment)?;
+                overrides.insert(name, value);
+            }
+            _ => return Err(format!("unknown option {arg}")),
+        }
+    }
+    Ok((json, overrides))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::tests::{fixture_lines, FIXTURE};
+
+    #[test]
+    fn test_run_fixture() {
+        let run = run(&FIXTURE, &fixture_lines(), &Params::new(), None).unwrap();
+        assert_eq!(run.answers.part_one, Some(12));
+        assert_eq!(
+            run.verdicts,
+            [Verdict::Unverified(12), Verdict::Unverified(42)]
+        );
+        assert_eq!(run.memory, None);
+
+        let text = run.to_string();
+        assert!(text.starts_with("2000 day 1\n"));
+        assert_eq!(text.lines().count(), 4);
+
+        let json = run.to_json();
+        assert_eq!(json.get("year"), Some(&Json::Integer(2000)));
+        let Some(Json::Array(parts)) = json.get("parts") else {
+            panic!("parts is an array");
+        };
+        assert_eq!(parts[1].get("answer"), Some(&Json::Integer(42)));
+        assert_eq!(parts[1].get("status"), Some(&Json::from("unverified")));
+        assert_eq!(parts[1].get("expected"), Some(&Json::Null));
+    }
+
+    #[test]
+    fn test_summary_counts_verdicts() {
+        let mut failing = run(&FIXTURE, &fixture_lines(), &Params::new(), None).unwrap();
+        failing.verdicts[0] = Verdict::Fail {
+            expected: 1,
+            actual: Some(12),
+        };
+        let passing = DayRun {
+            verdicts: [Verdict::Pass, Verdict::Unsolved],
+            ..failing.clone()
+        };
+        let timed_out = DayRun {
+            timed_out: [false, true, false],
+            ..passing.clone()
+        };
+
+        let summary = summary_json(&[failing, passing, timed_out], &[]);
+        let totals = summary.get("summary").unwrap();
+        assert_eq!(totals.get("days"), Some(&Json::Integer(3)));
+        assert_eq!(totals.get("passed"), Some(&Json::Integer(1)));
+        assert_eq!(totals.get("failed"), Some(&Json::Integer(1)));
+        assert_eq!(totals.get("unverified"), Some(&Json::Integer(1)));
+        assert_eq!(totals.get("unsolved"), Some(&Json::Integer(2)));
+        assert_eq!(totals.get("timed_out"), Some(&Json::Integer(1)));
+        assert!(summary
+            .to_string()
+            .contains("\"status\":\"fail\",\"expected\":1"));
+    }
+
+    #[test]
+    fn test_a_panic_is_an_error() {
+        let lines = vec!["x".to_string()];
+        for limit in [None, Some(Duration::from_secs(5))] {
+            let error = run(&FIXTURE, &lines, &Params::new(), limit).unwrap_err();
+            assert!(
+                error.starts_with("solution panicked: called `Result::unwrap()`"),
+                "{error}"
+            );
+        }
+
+        let error = DayError::new(&FIXTURE, "no input".to_string());
+        assert_eq!(error.brief(), "2000 day 1     ERROR no input");
+        assert_eq!(error.to_string(), "2000 day 1\n  ERROR no input\n");
+        let summary = summary_json(&[], &[error]);
+        assert_eq!(
+            summary.get("summary").unwrap().get("errors"),
+            Some(&Json::Integer(1))
+        );
+        assert!(summary
+            .to_string()
+            .contains("\"errors\":[{\"year\":2000,\"day\":1,\"error\":\"no input\"}]"));
+    }
+
+    /// Part two never finishes on its own; it gives up once cancelled
+    /// when the input is `cooperate` and otherwise spins forever.
+    struct Spinner;
+
+    impl crate::Solution for Spinner {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 2;
+
+        type Input = bool;
+
+        fn parse(lines: Vec<String>) -> bool {
+            lines == ["cooperate"]
+        }
+
+        fn part_one(_cooperate: &bool) -> Option<u64> {
+            Some(7)
+        }
+
+        fn part_two(cooperate: &bool) -> Option<u64> {
+            loop {
+                if cancel::is_cancelled() && *cooperate {
+                    return Some(1);
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+    }
+
+    const SPINNER: Day = Day::new::<Spinner>("spinner");
+
+    #[test]
+    fn test_timeout_cancels_a_stage() {
+        let limit = Some(Duration::from_millis(50));
+        for cooperate in [true, false] {
+            let lines = vec![if cooperate { "cooperate" } else { "spin" }.to_string()];
+            let run = run(&SPINNER, &lines, &Params::new(), limit).unwrap();
+            assert_eq!(run.answers.part_one, Some(7));
+            assert_eq!(run.answers.part_two, None);
+            assert_eq!(run.timed_out, [false, false, true]);
+            assert_eq!(run.status(Stage::PartTwo), "timeout");
+            assert_eq!(run.memory, None);
+            assert!(run.to_string().contains("TIMEOUT"));
+        }
+
+        let run = run(&FIXTURE, &fixture_lines(), &Params::new(), limit).unwrap();
+        assert_eq!(run.answers.part_two, Some(42));
+        assert_eq!(run.timed_out, [false; 3]);
+    }
+
+    /// Stalls in the stage named on the first line until cancelled, and
+    /// past that too unless the second line is `cooperate`.
+    struct Stall;
+
+    fn stall(lines: &[String]) {
+        while !(cancel::is_cancelled() && lines[1] == "cooperate") {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    impl crate::Solution for Stall {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 3;
+
+        type Input = Vec<String>;
+
+        fn parse(lines: Vec<String>) -> Vec<String> {
+            if lines[0] == "parse" {
+                stall(&lines);
+            }
+            lines
+        }
+
+        fn part_one(lines: &Vec<String>) -> Option<u64> {
+            if lines[0] == "part one" {
+                stall(lines);
+            }
+            Some(7)
+        }
+
+        fn part_two(_lines: &Vec<String>) -> Option<u64> {
+            Some(8)
+        }
+    }
+
+    #[test]
+    fn test_stages_after_a_timeout_are_not_failed() {
+        let dir = crate::tests::scratch_dir("run-stall");
+        std::fs::write(dir.join("answers.toml"), "part_two = 8\n").unwrap();
+        let day = Day::new::<Stall>(dir.to_string_lossy().into_owned().leak());
+        let limit = Some(Duration::from_millis(50));
+
+        for (stage, cooperate, timed_out, part_two) in [
+            ("parse", "cooperate", [true; 3], None),
+            ("parse", "spin", [true; 3], None),
+            ("part one", "cooperate", [false, true, false], Some(8)),
+            ("part one", "spin", [false, true, true], None),
+        ] {
+            let lines = vec![stage.to_string(), cooperate.to_string()];
+            let run = run(&day, &lines, &Params::new(), limit).unwrap();
+            assert_eq!(run.timed_out, timed_out, "{lines:?}");
+            assert_eq!(run.answers.part_one, None);
+            assert_eq!(run.answers.part_two, part_two);
+            assert!(!run.brief().contains("FAIL"), "{}", run.brief());
+
+            let summary = summary_json(&[run], &[]);
+            let totals = summary.get("summary").unwrap();
+            assert_eq!(totals.get("failed"), Some(&Json::Integer(0)));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
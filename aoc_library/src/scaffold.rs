@@ -0,0 +1,256 @@
+//! Generating a new day crate from the templates in `templates/day` and
+//! registering it with the runner.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Template files and where they land inside the new crate.
+const TEMPLATES: &[(&str, &str)] = &[
+    (
+        "Cargo.toml",
+        include_str!("../templates/day/Cargo.toml.tmpl"),
+    ),
+    ("build.rs", include_str!("../templates/day/build.rs.tmpl")),
+    ("src/lib.rs", include_str!("../templates/day/lib.rs.tmpl")),
+    ("src/main.rs", include_str!("../templates/day/main.rs.tmpl")),
+    ("examples/example.txt", ""),
+    (
+        "examples/example.toml",
+        include_str!("../templates/day/example.toml.tmpl"),
+    ),
+    (
+        "answers.toml",
+        include_str!("../templates/day/answers.toml.tmpl"),
+    ),
+    ("input.txt", ""),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewDay {
+    pub year: u16,
+    pub day: u8,
+    /// Package name of the generated crate. Includes the year, since every
+    /// year has a `day_1` directory but package names are workspace-wide.
+    pub crate_name: String,
+    /// Path of `aoc_library` relative to the generated crate.
+    pub library: String,
+}
+
+impl NewDay {
+    pub fn new(year: u16, day: u8) -> NewDay {
+        NewDay {
+            year,
+            day,
+            crate_name: format!("aoc_{year}_day_{day}"),
+            library: "../../aoc_library".to_string(),
+        }
+    }
+
+    /// The crate directory relative to the workspace root.
+    pub fn relative_dir(&self) -> PathBuf {
+        Path::new(&self.year.to_string()).join(format!("day_{}", self.day))
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{year}}", &self.year.to_string())
+            .replace("{{day}}", &self.day.to_string())
+            .replace("{{crate}}", &self.crate_name)
+            .replace("{{library}}", &self.library)
+    }
+}
+
+/// Writes the new crate below `workspace`, refusing to touch an existing
+/// directory. Returns the crate directory.
+pub fn generate(workspace: &Path, new_day: &NewDay) -> Result<PathBuf, String> {
+    if !(1..=25).contains(&new_day.day) {
+        return Err(format!("day {} is outside 1..=25", new_day.day));
+    }
+    let dir = workspace.join(new_day.relative_dir());
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    for (file, template) in TEMPLATES {
+        let path = dir.join(file);
+        let written = fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| fs::write(&path, new_day.render(template)));
+        if let Err(error) = written {
+            let _ = fs::remove_dir_all(&dir);
+            return Err(format!("{}: {error}", path.display()));
+        }
+    }
+    Ok(dir)
+}
+
+/// Generates the new crate and registers it with the runner in `runner`,
+/// removing the crate again if it cannot be registered.
+pub fn create(workspace: &Path, runner: &Path, new_day: &NewDay) -> Result<PathBuf, String> {
+    let dir = generate(workspace, new_day)?;
+    if let Err(error) = register(runner, new_day) {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(error);
+    }
+    Ok(dir)
+}
+
+/// Adds the new crate to the runner: a path dependency in its
+/// `Cargo.toml` and an entry in the `DAYS` list of `src/registry.rs`.
+pub fn register(runner: &Path, new_day: &NewDay) -> Result<(), String> {
+    let manifest_path = runner.join("Cargo.toml");
+    let registry_path = runner.join("src").join("registry.rs");
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|error| format!("{}: {error}", path.display()))
+    };
+    let write = |path: &Path, text: String| {
+        fs::write(path, text).map_err(|error| format!("{}: {error}", path.display()))
+    };
+
+    let dependency = format!(
+        "{} = {{ path = \"../{}\" }}",
+        new_day.crate_name,
+        new_day.relative_dir().display()
+    );
+    let manifest = insert_dependency(&read(&manifest_path)?, &dependency)?;
+
+    let entry = format!("{}::DAY", new_day.crate_name);
+    let registry = insert_registry_entry(&read(&registry_path)?, &entry)?;
+
+    write(&manifest_path, manifest)?;
+    write(&registry_path, registry)
+}
+
+fn insert_dependency(manifest: &str, dependency: &str) -> Result<String, String> {
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")
+        .ok_or("runner manifest has no [dependencies] section")?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.trim().is_empty() || line.starts_with('['))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+
+    let name = dependency.split(' ').next().unwrap_or_default();
+    if lines[start + 1..end]
+        .iter()
+        .any(|line| line.split(' ').next() == Some(name))
+    {
+        return Err(format!("{name} is already a runner dependency"));
+    }
+    lines.insert(end, dependency);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn insert_registry_entry(registry: &str, entry: &str) -> Result<String, String> {
+    const START: &str = "pub const DAYS: &[Day] = &[";
+    let start = registry.find(START).ok_or("registry has no DAYS list")? + START.len();
+    let end = start
+        + registry[start..]
+            .find("];")
+            .ok_or("registry DAYS list is not closed")?;
+
+    let mut entries: Vec<&str> = registry[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    if entries.contains(&entry) {
+        return Err(format!("{entry} is already registered"));
+    }
+    entries.push(entry);
+
+    let list: String = entries
+        .iter()
+        .map(|entry| format!("\n    {entry},"))
+        .collect();
+    Ok(format!(
+        "{}{list}\n{}",
+        &registry[..start],
+        &registry[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+
+    #[test]
+    fn test_generate_renders_templates() {
+        let workspace = scratch_dir("scaffold-generate");
+        let new_day = NewDay::new(2023, 5);
+        let dir = generate(&workspace, &new_day).unwrap();
+        assert_eq!(dir, workspace.join("2023/day_5"));
+
+        let lib = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day5;"));
+        assert!(lib.contains("const YEAR: u16 = 2023;"));
+        assert!(!lib.contains("{{"));
+        let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"aoc_2023_day_5\""));
+        assert!(manifest.contains("path = \"../../aoc_library\""));
+        assert!(dir.join("examples/example.txt").exists());
+        assert!(dir.join("input.txt").exists());
+
+        assert!(generate(&workspace, &new_day)
+            .unwrap_err()
+            .contains("already exists"));
+        assert!(generate(&workspace, &NewDay::new(2023, 26)).is_err());
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    #[test]
+    fn test_create_removes_an_unregistered_day() {
+        let workspace = scratch_dir("scaffold-create");
+        let runner = workspace.join("aoc");
+        fs::create_dir_all(runner.join("src")).unwrap();
+        fs::write(runner.join("Cargo.toml"), "[dependencies]\n").unwrap();
+
+        let new_day = NewDay::new(2023, 6);
+        assert!(create(&workspace, &runner, &new_day)
+            .unwrap_err()
+            .contains("registry.rs"));
+        assert!(!workspace.join("2023/day_6").exists());
+
+        fs::write(
+            runner.join("src/registry.rs"),
+            "pub const DAYS: &[Day] = &[];\n",
+        )
+        .unwrap();
+        let dir = create(&workspace, &runner, &new_day).unwrap();
+        assert!(dir.join("src/lib.rs").exists());
+        let registry = fs::read_to_string(runner.join("src/registry.rs")).unwrap();
+        assert!(registry.contains("aoc_2023_day_6::DAY"));
+        fs::remove_dir_all(&workspace).unwrap();
+    }
+
+    #[test]
+    fn test_insert_dependency() {
+        let manifest = "[package]\nname = \"aoc\"\n\n[dependencies]\nday_1 = { path = \"../2023/day_1\" }\n\n[features]\nx = []\n";
+        let updated = insert_dependency(manifest, "day_2 = { path = \"../2023/day_2\" }").unwrap();
+        assert_eq!(
+            updated,
+            "[package]\nname = \"aoc\"\n\n[dependencies]\nday_1 = { path = \"../2023/day_1\" }\nday_2 = { path = \"../2023/day_2\" }\n\n[features]\nx = []\n"
+        );
+        assert!(insert_dependency(&updated, "day_2 = { path = \"x\" }").is_err());
+    }
+
+    #[test]
+    fn test_insert_registry_entry() {
+        let registry = "use aoc_library::Day;\n\npub const DAYS: &[Day] = &[day_1::DAY, day_2::DAY];\n\npub fn find() {}\n";
+        let updated = insert_registry_entry(registry, "day_3::DAY").unwrap();
+        assert_eq!(
+            updated,
+            "use aoc_library::Day;\n\npub const DAYS: &[Day] = &[\n    day_1::DAY,\n    day_2::DAY,\n    day_3::DAY,\n];\n\npub fn find() {}\n"
+        );
+        assert_eq!(
+            insert_registry_entry(&updated, "day_4::DAY")
+                .unwrap()
+                .matches("::DAY")
+                .count(),
+            4
+        );
+        assert!(insert_registry_entry(&updated, "day_3::DAY").is_err());
+    }
+}
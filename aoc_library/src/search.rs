@@ -0,0 +1,515 @@
+//! Shortest paths over any graph given by a neighbour function.
+//!
+//! Nodes are anything `Clone + Eq + Hash`, such as a grid
+//! [`Pos`](crate::grid::Pos) or a `(Pos, direction)` state. [`bfs`],
+//! [`dijkstra`] and [`astar`] take the starting nodes, the neighbours of a
+//! node (with edge costs for the latter two) and a goal test, and return
+//! what they [`Reached`]: the distance to every node found and the way back
+//! along every shortest path. They stop once no shorter path to a goal can
+//! exist; a goal test of `|_| false` explores everything reachable.
+//!
+//! ```ignore
+//! let maze = Grid::from_ascii(&lines)?;
+//! let start = maze.position(|&c| c == 'S').unwrap();
+//! let reached = search::bfs(
+//!     [start],
+//!     |&pos| maze.neighbours(pos).filter(|&next| maze[next] != '#'),
+//!     |&pos| maze[pos] == 'E',
+//! );
+//! let steps = reached.goal_path().map(|path| path.cost);
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A path from a start to a node, both included, and what it costs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: u64,
+    pub nodes: Vec<N>,
+}
+
+/// The nodes a search found, with their distances and shortest-path
+/// parents.
+#[derive(Debug, Clone)]
+pub struct Reached<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    distance: Vec<u64>,
+    /// Every node one step back on some shortest path; empty for starts.
+    parents: Vec<Vec<usize>>,
+    goal: Option<usize>,
+}
+
+impl<N: Clone + Eq + Hash> Reached<N> {
+    fn new() -> Reached<N> {
+        Reached {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            distance: Vec::new(),
+            parents: Vec::new(),
+            goal: None,
+        }
+    }
+
+    /// Records that `node` can be reached at `distance` from `parent`.
+    /// Returns the node's index if this is its first or a shorter path,
+    /// so it needs to be explored (again).
+    fn offer(&mut self, node: N, distance: u64, parent: Option<usize>) -> Option<usize> {
+        let parents = Vec::from_iter(parent);
+        match self.index.get(&node) {
+            None => {
+                let id = self.nodes.len();
+                self.index.insert(node.clone(), id);
+                self.nodes.push(node);
+                self.distance.push(distance);
+                self.parents.push(parents);
+                Some(id)
+            }
+            Some(&id) if distance < self.distance[id] => {
+                self.distance[id] = distance;
+                self.parents[id] = parents;
+                Some(id)
+            }
+            // Starts keep no parents. Zero-cost edges can make two nodes
+            // each other's parents, which the walks back along parents
+            // allow for.
+            Some(&id) => {
+                let is_start = self.parents[id].is_empty();
+                if !is_start && distance == self.distance[id] {
+                    if let Some(parent) =
+                        parent.filter(|&parent| parent != id && !self.parents[id].contains(&parent))
+                    {
+                        self.parents[id].push(parent);
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// The length of a shortest path to `node`, if the search reached it.
+    pub fn distance(&self, node: &N) -> Option<u64> {
+        self.index.get(node).map(|&id| self.distance[id])
+    }
+
+    /// Every node reached with its distance, in the order they were found.
+    pub fn distances(&self) -> impl Iterator<Item = (&N, u64)> {
+        self.nodes.iter().zip(self.distance.iter().copied())
+    }
+
+    /// The number of nodes reached.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The first goal the search settled, if any.
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.map(|id| &self.nodes[id])
+    }
+
+    /// A shortest path to the goal.
+    pub fn goal_path(&self) -> Option<Path<N>> {
+        self.goal.map(|id| self.path_from(id))
+    }
+
+    /// A shortest path to `node`.
+    pub fn path_to(&self, node: &N) -> Option<Path<N>> {
+        self.index.get(node).map(|&id| self.path_from(id))
+    }
+
+    /// Follows first parents, which never loop: a node's first parent was
+    /// explored before it was reached.
+    fn path_from(&self, mut id: usize) -> Path<N> {
+        let cost = self.distance[id];
+        let mut nodes = vec![self.nodes[id].clone()];
+        while let Some(&parent) = self.parents[id].first() {
+            nodes.push(self.nodes[parent].clone());
+            id = parent;
+        }
+        nodes.reverse();
+        Path { cost, nodes }
+    }
+
+    /// Every shortest path to `node` that visits no node twice, which only
+    /// zero-cost loops could make it do. There can be exponentially many;
+    /// [`on_shortest_paths`](Reached::on_shortest_paths) is cheaper when
+    /// only the nodes matter.
+    pub fn all_paths_to(&self, node: &N) -> Vec<Path<N>> {
+        let Some(&id) = self.index.get(node) else {
+            return Vec::new();
+        };
+        let mut paths = Vec::new();
+        let mut stack = vec![vec![id]];
+        while let Some(partial) = stack.pop() {
+            let last = *partial.last().expect("partial paths are never empty");
+            if self.parents[last].is_empty() {
+                let nodes = partial.iter().rev().map(|&id| self.nodes[id].clone());
+                paths.push(Path {
+                    cost: self.distance[id],
+                    nodes: nodes.collect(),
+                });
+                continue;
+            }
+            // A parent already on the path is a zero-cost loop back.
+            for &parent in self.parents[last].iter().rev() {
+                if partial.contains(&parent) {
+                    continue;
+                }
+                let mut longer = partial.clone();
+                longer.push(parent);
+                stack.push(longer);
+            }
+        }
+        paths
+    }
+
+    /// Every node on some shortest path to `node`, `node` included.
+    pub fn on_shortest_paths(&self, node: &N) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut stack = Vec::from_iter(self.index.get(node).copied());
+        while let Some(id) = stack.pop() {
+            if seen.insert(id) {
+                stack.extend(&self.parents[id]);
+            }
+        }
+        seen.into_iter().map(|id| self.nodes[id].clone()).collect()
+    }
+}
+
+/// Breadth-first search: every edge costs one.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Reached<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new();
+    let mut queue: VecDeque<usize> = starts
+        .into_iter()
+        .filter_map(|start| reached.offer(start, 0, None))
+        .collect();
+    // A node's parents are all found by the time it leaves the queue, as
+    // every node one step closer left before it.
+    while let Some(id) = queue.pop_front() {
+        if is_goal(&reached.nodes[id]) {
+            reached.goal = Some(id);
+            break;
+        }
+        let distance = reached.distance[id] + 1;
+        for next in neighbours(&reached.nodes[id].clone()) {
+            queue.extend(reached.offer(next, distance, Some(id)));
+        }
+    }
+    reached
+}
+
+/// Dijkstra's algorithm over edges with non-negative costs.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Reached<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    astar(starts, neighbours, |_| 0, is_goal)
+}
+
+/// A* search: Dijkstra's algorithm guided by `heuristic`, an estimate of
+/// the remaining cost to a goal that must never overestimate it nor drop
+/// by more than an edge's cost along the edge, such as
+/// [`manhattan`](crate::grid::manhattan) distance on a grid.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Reached<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut reached = Reached::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let Some(id) = reached.offer(start, 0, None) {
+            queue.push(Reverse((heuristic(&reached.nodes[id]), 0, id)));
+        }
+    }
+    while let Some(Reverse((estimate, distance, id))) = queue.pop() {
+        if distance > reached.distance[id] {
+            continue;
+        }
+        // Keep going through nodes as cheap as the goal, which may be
+        // further parents of it on equally short paths.
+        if let Some(goal) = reached.goal {
+            if estimate > reached.distance[goal] {
+                break;
+            }
+        } else if is_goal(&reached.nodes[id]) {
+            reached.goal = Some(id);
+        }
+        for (next, cost) in neighbours(&reached.nodes[id].clone()) {
+            let total = distance
+                .checked_add(cost)
+                .unwrap_or_else(|| panic!("u64 overflow in path cost"));
+            if let Some(next) = reached.offer(next, total, Some(id)) {
+                let estimate = total.saturating_add(heuristic(&reached.nodes[next]));
+                queue.push(Reverse((estimate, total, next)));
+            }
+        }
+    }
+    reached
+}
+
+/// Breadth-first search from both ends at once, for a shortest path
+/// between two nodes in a large graph. Edges must work both ways:
+/// `neighbours` is followed backwards from `goal`.
+pub fn bidirectional_bfs<N, I>(
+    start: N,
+    goal: N,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    if start == goal {
+        return Some(Path {
+            cost: 0,
+            nodes: vec![start],
+        });
+    }
+    // Each side maps the nodes it reached to the node it came from.
+    let mut sides = [
+        HashMap::from([(start.clone(), None)]),
+        HashMap::from([(goal.clone(), None)]),
+    ];
+    let mut frontiers = [vec![start], vec![goal]];
+    loop {
+        // Grow the smaller frontier by one whole level.
+        let side = usize::from(frontiers[1].len() < frontiers[0].len());
+        if frontiers[side].is_empty() {
+            return None;
+        }
+        let mut next_level = Vec::new();
+        let mut meeting = None;
+        for node in std::mem::take(&mut frontiers[side]) {
+            for next in neighbours(&node) {
+                if sides[side].contains_key(&next) {
+                    continue;
+                }
+                sides[side].insert(next.clone(), Some(node.clone()));
+                if meeting.is_none() && sides[1 - side].contains_key(&next) {
+                    meeting = Some(next.clone());
+                }
+                next_level.push(next);
+            }
+        }
+        frontiers[side] = next_level;
+
+        if let Some(meeting) = meeting {
+            let walk = |side: &HashMap<N, Option<N>>| {
+                let mut nodes = vec![meeting.clone()];
+                while let Some(Some(parent)) = side.get(nodes.last().unwrap()) {
+                    nodes.push(parent.clone());
+                }
+                nodes
+            };
+            let mut nodes = walk(&sides[0]);
+            nodes.reverse();
+            nodes.extend(walk(&sides[1]).into_iter().skip(1));
+            return Some(Path {
+                cost: (nodes.len() - 1) as u64,
+                nodes,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{manhattan, Grid, Pos};
+
+    const MAZE: &[&str] = &["S..#....", ".#.#.##.", ".#...#..", ".####.#.", "......#E"];
+
+    fn maze() -> Grid<char> {
+        let lines: Vec<String> = MAZE.iter().map(|row| row.to_string()).collect();
+        Grid::from_ascii(&lines).unwrap()
+    }
+
+    fn open(grid: &Grid<char>, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        grid.neighbours(pos).filter(|&next| grid[next] != '#')
+    }
+
+    #[test]
+    fn test_bfs_on_grid() {
+        let grid = maze();
+        let start = grid.position(|&c| c == 'S').unwrap();
+        let end = grid.position(|&c| c == 'E').unwrap();
+        let reached = bfs([start], |&pos| open(&grid, pos), |&pos| pos == end);
+        let path = reached.goal_path().unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.first(), Some(&start));
+        assert_eq!(path.nodes.last(), Some(&end));
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|step| manhattan(step[0], step[1]) == 1));
+
+        let everything = bfs([start], |&pos| open(&grid, pos), |_| false);
+        assert_eq!(everything.goal(), None);
+        assert_eq!(
+            everything.len(),
+            grid.iter().filter(|(_, &c)| c != '#').count()
+        );
+        assert_eq!(everything.distance(&(3, 2)), Some(5));
+        assert_eq!(everything.distance(&(3, 0)), None);
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        // Two ways around the middle of a 3x3 ring.
+        let grid = Grid::from_ascii(&["...", ".#.", "..."].map(String::from)).unwrap();
+        let reached = bfs([(0, 0)], |&pos| open(&grid, pos), |&pos| pos == (2, 2));
+        let paths = reached.all_paths_to(&(2, 2));
+        assert_eq!(paths.len(), 2);
+        assert!(paths
+            .iter()
+            .all(|path| path.cost == 4 && path.nodes.len() == 5));
+        assert_eq!(reached.on_shortest_paths(&(2, 2)).len(), 8);
+        assert!(reached.all_paths_to(&(1, 1)).is_empty());
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar_agree() {
+        // Stepping onto a digit costs that digit.
+        let grid = Grid::from_ascii(&["1163", "1381", "2136"].map(String::from)).unwrap();
+        let cost = |pos: Pos| u64::from(grid[pos].to_digit(10).unwrap());
+        let weighted = |&pos: &Pos| grid.neighbours(pos).map(move |next| (next, cost(next)));
+        let end = (3, 2);
+
+        let plain = dijkstra([(0, 0)], weighted, |&pos| pos == end);
+        let guided = astar(
+            [(0, 0)],
+            weighted,
+            |&pos| manhattan(pos, end),
+            |&pos| pos == end,
+        );
+        assert_eq!(plain.goal_path().unwrap().cost, 13);
+        assert_eq!(guided.goal_path(), plain.goal_path());
+        assert!(guided.len() <= plain.len());
+        assert_eq!(plain.all_paths_to(&end), guided.all_paths_to(&end));
+    }
+
+    #[test]
+    fn test_weighted_ties_keep_every_parent() {
+        // 0 -> 1 -> 3 and 0 -> 2 -> 3 both cost 3; 0 -> 3 directly costs 5.
+        let edges = |&node: &u8| match node {
+            0 => vec![(1, 1), (2, 2), (3, 5)],
+            1 => vec![(3, 2)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        let reached = dijkstra([0], edges, |&node| node == 3);
+        assert_eq!(reached.distance(&3), Some(3));
+        let mut paths: Vec<_> = reached
+            .all_paths_to(&3)
+            .into_iter()
+            .map(|path| path.nodes)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, [vec![0, 1, 3], vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn test_zero_cost_edges_leave_no_parent_loops() {
+        let back_and_forth = |&node: &u8| {
+            if node == 0 {
+                vec![(1, 0)]
+            } else {
+                vec![(0, 0)]
+            }
+        };
+        let reached = dijkstra([0u8], back_and_forth, |&node| node == 1);
+        let path = reached.goal_path().unwrap();
+        assert_eq!((path.cost, path.nodes), (0, vec![0, 1]));
+
+        // 0 and 1 are both starts, joined both ways for free, and 2 hangs
+        // off 1 with a zero-cost loop of its own.
+        let edges = |&node: &u8| match node {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 0)],
+            _ => vec![(2, 0), (1, 0)],
+        };
+        let reached = dijkstra([0, 1], edges, |_| false);
+        assert_eq!(reached.path_to(&1).unwrap().nodes, [1]);
+        assert_eq!(reached.all_paths_to(&2).len(), 1);
+        assert_eq!(reached.on_shortest_paths(&2), HashSet::from([1, 2]));
+    }
+
+    #[test]
+    fn test_zero_cost_edges_keep_equally_short_paths() {
+        // S = 0 reaches A = 1 and B = 2 for 1 each, and A and B are joined
+        // both ways for free, so S -> B -> A is as short as S -> A.
+        let edges = |&node: &u8| match node {
+            0 => vec![(1, 1), (2, 1)],
+            1 => vec![(2, 0)],
+            _ => vec![(1, 0)],
+        };
+        let reached = dijkstra([0], edges, |_| false);
+        assert_eq!(reached.path_to(&1).unwrap().nodes, [0, 1]);
+        let mut paths: Vec<_> = reached
+            .all_paths_to(&1)
+            .into_iter()
+            .map(|path| path.nodes)
+            .collect();
+        paths.sort();
+        assert_eq!(paths, [vec![0, 1], vec![0, 2, 1]]);
+        assert_eq!(reached.on_shortest_paths(&1), HashSet::from([0, 1, 2]));
+        assert_eq!(reached.all_paths_to(&2).len(), 2);
+        assert_eq!(reached.path_to(&2).unwrap().nodes, [0, 2]);
+    }
+
+    #[test]
+    fn test_bidirectional_bfs() {
+        let grid = maze();
+        let (start, end) = ((0, 0), (7, 4));
+        let path = bidirectional_bfs(start, end, |&pos| open(&grid, pos).collect::<Vec<_>>());
+        let path = path.unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.len(), 16);
+        assert_eq!((path.nodes[0], path.nodes[15]), (start, end));
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|step| manhattan(step[0], step[1]) == 1));
+
+        assert_eq!(
+            bidirectional_bfs(start, start, |_| Vec::new())
+                .unwrap()
+                .cost,
+            0
+        );
+        // 0 - 1 - 2 and 5 - 6 are not connected.
+        let line = |&node: &u8| match node {
+            0 | 2 => vec![1],
+            1 => vec![0, 2],
+            5 => vec![6],
+            _ => vec![5],
+        };
+        assert_eq!(bidirectional_bfs(0, 2, line).unwrap().nodes, [0, 1, 2]);
+        assert_eq!(bidirectional_bfs(0, 6, line), None);
+    }
+}
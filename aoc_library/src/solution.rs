@@ -0,0 +1,234 @@
+//! The common shape of a day's solution and the type-erased [`Day`] the
+//! runner drives.
+
+use crate::params::Param;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// A puzzle solution split into the stages the runner times separately.
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+
+    /// Named values the solution reads with [`crate::params::get_or`].
+    const PARAMS: &'static [Param] = &[];
+
+    type Input;
+
+    fn parse(lines: Vec<String>) -> Self::Input;
+
+    fn part_one(input: &Self::Input) -> Option<u64>;
+
+    /// Days that have only solved part one keep the default.
+    fn part_two(_input: &Self::Input) -> Option<u64> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Stage {
+    Parse,
+    PartOne,
+    PartTwo,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::PartOne, Stage::PartTwo];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::PartOne => "part one",
+            Stage::PartTwo => "part two",
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Wraps the execution of each stage. Implementations decide how often to
+/// call `run` and what to record around it.
+pub trait Meter {
+    fn measure(&mut self, stage: Stage, run: &mut dyn FnMut());
+
+    /// Sees each part's answer as soon as the part has run.
+    fn answer(&mut self, _stage: Stage, _answer: Option<u64>) {}
+}
+
+/// Runs every stage exactly once without recording anything.
+pub struct NoMeter;
+
+impl Meter for NoMeter {
+    fn measure(&mut self, _stage: Stage, run: &mut dyn FnMut()) {
+        run();
+    }
+}
+
+/// Runs every stage once and records its wall-clock time.
+#[derive(Debug, Default, Clone)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_one: Duration,
+    pub part_two: Duration,
+}
+
+impl Timings {
+    pub fn get(&self, stage: Stage) -> Duration {
+        match stage {
+            Stage::Parse => self.parse,
+            Stage::PartOne => self.part_one,
+            Stage::PartTwo => self.part_two,
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_one + self.part_two
+    }
+}
+
+impl Meter for Timings {
+    fn measure(&mut self, stage: Stage, run: &mut dyn FnMut()) {
+        let start = Instant::now();
+        run();
+        let elapsed = start.elapsed();
+        match stage {
+            Stage::Parse => self.parse = elapsed,
+            Stage::PartOne => self.part_one = elapsed,
+            Stage::PartTwo => self.part_two = elapsed,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<u64>,
+    pub part_two: Option<u64>,
+}
+
+/// A registered day: where it lives and how to solve it.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    /// The day crate's directory, normally `env!("CARGO_MANIFEST_DIR")`.
+    pub dir: &'static str,
+    pub params: &'static [Param],
+    solve: fn(&[String], &mut dyn Meter) -> Answers,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(dir: &'static str) -> Day {
+        Day {
+            year: S::YEAR,
+            day: S::DAY,
+            dir,
+            params: S::PARAMS,
+            solve: solve::<S>,
+        }
+    }
+
+    /// Parses `lines` and solves both parts, handing each stage to `meter`.
+    /// The parse stage includes copying `lines` into the solution.
+    pub fn solve(&self, lines: &[String], meter: &mut dyn Meter) -> Answers {
+        (self.solve)(lines, meter)
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Day")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("dir", &self.dir)
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+impl fmt::Display for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+fn solve<S: Solution>(lines: &[String], meter: &mut dyn Meter) -> Answers {
+    let mut input = None;
+    meter.measure(Stage::Parse, &mut || {
+        input = Some(S::parse(lines.to_vec()));
+    });
+    let input = input.expect("meter must run the parse stage");
+
+    let mut answers = Answers::default();
+    meter.measure(Stage::PartOne, &mut || {
+        answers.part_one = S::part_one(&input);
+    });
+    meter.answer(Stage::PartOne, answers.part_one);
+    meter.measure(Stage::PartTwo, &mut || {
+        answers.part_two = S::part_two(&input);
+    });
+    meter.answer(Stage::PartTwo, answers.part_two);
+    answers
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Sums the numbers on each line for part one and multiplies them for
+    /// part two. Its parameter is only there to be configured.
+    pub struct Fixture;
+
+    impl Solution for Fixture {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+        const PARAMS: &'static [Param] = &[Param::new("steps", 64, 6)];
+
+        type Input = Vec<u64>;
+
+        fn parse(lines: Vec<String>) -> Vec<u64> {
+            lines.iter().map(|line| line.parse().unwrap()).collect()
+        }
+
+        fn part_one(input: &Vec<u64>) -> Option<u64> {
+            Some(input.iter().sum())
+        }
+
+        fn part_two(input: &Vec<u64>) -> Option<u64> {
+            Some(input.iter().product())
+        }
+    }
+
+    pub const FIXTURE: Day = Day::new::<Fixture>("fixture");
+
+    pub fn fixture_lines() -> Vec<String> {
+        ["2", "3", "7"].iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_day_solves_all_stages() {
+        let mut timings = Timings::default();
+        let answers = FIXTURE.solve(&fixture_lines(), &mut timings);
+        assert_eq!(answers.part_one, Some(12));
+        assert_eq!(answers.part_two, Some(42));
+        assert_eq!(FIXTURE.to_string(), "2000 day 1");
+    }
+
+    #[test]
+    fn test_meter_sees_stages_in_order() {
+        struct Recorder(Vec<Stage>);
+        impl Meter for Recorder {
+            fn measure(&mut self, stage: Stage, run: &mut dyn FnMut()) {
+                self.0.push(stage);
+                run();
+            }
+        }
+
+        let mut recorder = Recorder(Vec::new());
+        FIXTURE.solve(&fixture_lines(), &mut recorder);
+        assert_eq!(recorder.0, Stage::ALL);
+    }
+}
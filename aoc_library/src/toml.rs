@@ -0,0 +1,322 @@
+//! The small subset of TOML used by answer, example and config files:
+//! `[dotted.section]` headers and `key = value` pairs holding integers,
+//! strings or booleans, with `#` comments. Integers run from `i64::MIN` to
+//! `u64::MAX`, so any answer fits, and strings take the escapes `\"`,
+//! `\\`, `\n`, `\t`, `\r`, `\uXXXX` and `\UXXXXXXXX`.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::str::Chars;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Integer(i128),
+    String(String),
+    Boolean(bool),
+}
+
+impl Value {
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Value::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Integer(value) => write!(f, "{value}"),
+            Value::String(value) => write_string(f, value),
+            Value::Boolean(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Keys mapped to values, grouped by section. Top-level keys live in the
+/// section named `""`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Document {
+    pub sections: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+impl Document {
+    pub fn section(&self, name: &str) -> Option<&BTreeMap<String, Value>> {
+        self.sections.get(name)
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&Value> {
+        self.section(section)?.get(key)
+    }
+
+    pub fn set(&mut self, section: &str, key: &str, value: Value) {
+        self.sections
+            .entry(section.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (name, entries) in &self.sections {
+            if !name.is_empty() {
+                if !first {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[{name}]")?;
+            }
+            for (key, value) in entries {
+                writeln!(f, "{key} = {value}")?;
+            }
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// One-based line number.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn parse(text: &str) -> Result<Document, ParseError> {
+    let mut document = Document::default();
+    let mut section = String::new();
+
+    for (index, raw) in text.lines().enumerate() {
+        let error = |message: &str| ParseError {
+            line: index + 1,
+            message: message.to_string(),
+        };
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header
+                .strip_suffix(']')
+                .ok_or_else(|| error("unterminated section header"))?
+                .trim();
+            if name.is_empty() {
+                return Err(error("empty section name"));
+            }
+            section = name.to_string();
+            document.sections.entry(section.clone()).or_default();
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `key = value`"))?;
+        let key = unquote(key.trim()).unwrap_or(key.trim());
+        if key.is_empty() {
+            return Err(error("empty key"));
+        }
+        let value = parse_value(value.trim()).map_err(error)?;
+        document.set(&section, key, value);
+    }
+
+    Ok(document)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => (),
+        }
+    }
+    line
+}
+
+fn unquote(text: &str) -> Option<&str> {
+    text.strip_prefix('"')?.strip_suffix('"')
+}
+
+fn parse_value(text: &str) -> Result<Value, &'static str> {
+    match text {
+        "true" => return Ok(Value::Boolean(true)),
+        "false" => return Ok(Value::Boolean(false)),
+        _ => (),
+    }
+    if let Some(string) = unquote(text) {
+        return unescape(string)
+            .map(Value::String)
+            .ok_or("invalid escape in string");
+    }
+    let integer: i128 = text
+        .replace('_', "")
+        .parse()
+        .map_err(|_| "unsupported value")?;
+    if integer < i128::from(i64::MIN) || integer > i128::from(u64::MAX) {
+        return Err("integer out of range");
+    }
+    Ok(Value::Integer(integer))
+}
+
+fn unescape(text: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        unescaped.push(match chars.next()? {
+            '"' => '"',
+            '\\' => '\\',
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            'u' => code_point(&mut chars, 4)?,
+            'U' => code_point(&mut chars, 8)?,
+            _ => return None,
+        });
+    }
+    Some(unescaped)
+}
+
+fn code_point(chars: &mut Chars, digits: usize) -> Option<char> {
+    let hex: String = chars.by_ref().take(digits).collect();
+    if hex.len() != digits {
+        return None;
+    }
+    char::from_u32(u32::from_str_radix(&hex, 16).ok()?)
+}
+
+/// Writes `text` quoted, escaping what [`unescape`] reads back.
+fn write_string(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in text.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\t' => f.write_str("\\t")?,
+            '\r' => f.write_str("\\r")?,
+            c if c.is_control() => write!(f, "\\u{:04X}", u32::from(c))?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sections_and_values() {
+        let document = parse(
+            "# answers\n\
+             title = \"aoc # 2023\"\n\
+             \n\
+             [2023.1]\n\
+             part_one = 54_390 # star\n\
+             solved = true\n\
+             [day]\n\
+             \"quoted key\" = -4\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            document.get("", "title"),
+            Some(&Value::String("aoc # 2023".into()))
+        );
+        assert_eq!(
+            document.get("2023.1", "part_one").unwrap().as_integer(),
+            Some(54390)
+        );
+        assert_eq!(
+            document.get("2023.1", "solved").unwrap().as_bool(),
+            Some(true)
+        );
+        assert_eq!(
+            document.get("day", "quoted key").unwrap().as_integer(),
+            Some(-4)
+        );
+        assert_eq!(document.get("2023.1", "missing"), None);
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let error = parse("a = 1\n[broken\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            parse("a = [1, 2]").unwrap_err().message,
+            "unsupported value"
+        );
+        assert_eq!(
+            parse("just text").unwrap_err().to_string(),
+            "line 1: expected `key = value`"
+        );
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        let mut document = Document::default();
+        document.set("", "name", Value::String("aoc".into()));
+        document.set("2023.3", "part_two", Value::Integer(78826761));
+        document.set("2023.3", "part_one", Value::Integer(533784));
+        let text = document.to_string();
+        assert_eq!(
+            text,
+            "name = \"aoc\"\n\n[2023.3]\npart_one = 533784\npart_two = 78826761\n"
+        );
+        assert_eq!(parse(&text).unwrap(), document);
+    }
+
+    #[test]
+    fn test_strings_and_integers_round_trip() {
+        let mut document = Document::default();
+        let tricky = "say \"hi\" # C:\\aoc\n\tdone\r\u{1}é";
+        document.set("", "text", Value::String(tricky.into()));
+        document.set("", "largest", Value::Integer(u64::MAX.into()));
+        document.set("", "smallest", Value::Integer(i64::MIN.into()));
+        let text = document.to_string();
+        assert!(text.contains(r#"text = "say \"hi\" # C:\\aoc\n\tdone\r\u0001é""#));
+        assert_eq!(parse(&text).unwrap(), document);
+
+        assert_eq!(
+            parse("a = 18446744073709551616").unwrap_err().message,
+            "integer out of range"
+        );
+        assert_eq!(
+            parse(r#"a = "\q""#).unwrap_err().message,
+            "invalid escape in string"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Checking answers against the known-correct ones a day records in
+//! `answers.toml`:
+//!
+//! ```toml
+//! part_one = 54390
+//! part_two = 54277
+//! ```
+
+use crate::solution::{Answers, Day};
+use crate::toml;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Reads `part_one` and `part_two` from a TOML answers file. Either may be
+/// absent.
+pub fn load_answers(path: &Path) -> Result<Answers, String> {
+    let text = fs::read_to_string(path).map_err(|error| format!("{}: {error}", path.display()))?;
+    parse_answers(&text).map_err(|error| format!("{}: {error}", path.display()))
+}
+
+pub fn parse_answers(text: &str) -> Result<Answers, String> {
+    let document = toml::parse(text).map_err(|error| error.to_string())?;
+    let answer = |key: &str| -> Result<Option<u64>, String> {
+        document
+            .get("", key)
+            .map(|value| {
+                value
+                    .as_integer()
+                    .and_then(|value| u64::try_from(value).ok())
+                    .ok_or_else(|| format!("{key} must be a non-negative integer"))
+            })
+            .transpose()
+    };
+
+    Ok(Answers {
+        part_one: answer("part_one")?,
+        part_two: answer("part_two")?,
+    })
+}
+
+/// The day's recorded answers, or `None` when it has no answers file.
+pub fn expected_answers(day: &Day) -> Result<Option<Answers>, String> {
+    let path = Path::new(day.dir).join(ANSWERS_FILE);
+    match load_answers(&path) {
+        Ok(answers) => Ok(Some(answers)),
+        Err(_)
+            if fs::metadata(&path).is_err_and(|error| error.kind() == io::ErrorKind::NotFound) =>
+        {
+            Ok(None)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer matches the recorded one.
+    Pass,
+    /// The answer differs from the recorded one, or is missing.
+    Fail { expected: u64, actual: Option<u64> },
+    /// An answer was produced but nothing is recorded to check it against.
+    Unverified(u64),
+    /// Neither an answer nor a recorded answer exists.
+    Unsolved,
+}
+
+impl Verdict {
+    pub fn new(expected: Option<u64>, actual: Option<u64>) -> Verdict {
+        match (expected, actual) {
+            (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+            (Some(expected), actual) => Verdict::Fail { expected, actual },
+            (None, Some(actual)) => Verdict::Unverified(actual),
+            (None, None) => Verdict::Unsolved,
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail { .. })
+    }
+
+    /// One word for machine-readable output.
+    pub fn status(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::Unverified(_) => "unverified",
+            Verdict::Unsolved => "unsolved",
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Verdict::Pass => "pass".to_string(),
+            Verdict::Fail {
+                expected,
+                actual: Some(actual),
+            } => format!("FAIL (got {actual}, expected {expected})"),
+            Verdict::Fail {
+                expected,
+                actual: None,
+            } => format!("FAIL (no answer, expected {expected})"),
+            Verdict::Unverified(_) => "unverified".to_string(),
+            Verdict::Unsolved => "-".to_string(),
+        };
+        f.pad(&text)
+    }
+}
+
+/// Verdicts for part one and part two.
+pub fn verify(expected: &Answers, actual: &Answers) -> [Verdict; 2] {
+    [
+        Verdict::new(expected.part_one, actual.part_one),
+        Verdict::new(expected.part_two, actual.part_two),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers("# known good\npart_one = 24848\n").unwrap();
+        assert_eq!(answers.part_one, Some(24848));
+        assert_eq!(answers.part_two, None);
+        assert!(parse_answers("part_one = -1").is_err());
+        assert!(parse_answers("part_one = \"7\"").is_err());
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let expected = Answers {
+            part_one: Some(10),
+            part_two: Some(20),
+        };
+        let actual = Answers {
+            part_one: Some(10),
+            part_two: Some(21),
+        };
+        let [one, two] = verify(&expected, &actual);
+        assert_eq!(one, Verdict::Pass);
+        assert!(two.is_failure());
+        assert_eq!(two.to_string(), "FAIL (got 21, expected 20)");
+
+        assert!(Verdict::new(Some(1), None).is_failure());
+        assert_eq!(Verdict::new(None, Some(3)), Verdict::Unverified(3));
+        assert_eq!(Verdict::new(None, None).to_string(), "-");
+        assert_eq!(two.status(), "fail");
+        assert_eq!(Verdict::new(None, None).status(), "unsolved");
+        assert_eq!(format!("{:<6}|", Verdict::Pass), "pass  |");
+    }
+
+    #[test]
+    fn test_day_without_answers_file() {
+        let day = crate::solution::tests::FIXTURE;
+        assert_eq!(expected_answers(&day), Ok(None));
+    }
+}
@@ -0,0 +1,172 @@
+//! Polling a day's files for changes and comparing answers between runs,
+//! for the runner's watch mode.
+
+use crate::solution::{Answers, Stage};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Modification times of every file below some roots. Build output
+/// (`target`) and hidden directories are skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl Snapshot {
+    /// Roots may be files or directories; missing ones are ignored so a
+    /// file that appears later shows up as a change.
+    pub fn take(roots: &[PathBuf]) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        for root in roots {
+            snapshot.visit(root);
+        }
+        snapshot
+    }
+
+    fn visit(&mut self, path: &Path) {
+        let Ok(metadata) = fs::metadata(path) else {
+            return;
+        };
+        if metadata.is_dir() {
+            let Ok(entries) = fs::read_dir(path) else {
+                return;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                if name == "target" || name.starts_with('.') {
+                    continue;
+                }
+                self.visit(&entry.path());
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            self.files.insert(path.to_path_buf(), modified);
+        }
+    }
+
+    /// Every file found, sorted.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// Files added, removed or modified since `earlier`, sorted.
+    pub fn changes_since(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(path, modified)| earlier.files.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .chain(
+                earlier
+                    .files
+                    .keys()
+                    .filter(|path| !self.files.contains_key(*path))
+                    .cloned(),
+            )
+            .collect();
+        changed.sort();
+        changed
+    }
+}
+
+/// A part whose answer differs between two runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnswerChange {
+    pub stage: Stage,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+}
+
+/// The parts whose answers changed from `before` to `after`.
+pub fn diff(before: &Answers, after: &Answers) -> Vec<AnswerChange> {
+    [
+        (Stage::PartOne, before.part_one, after.part_one),
+        (Stage::PartTwo, before.part_two, after.part_two),
+    ]
+    .into_iter()
+    .filter(|(_, before, after)| before != after)
+    .map(|(stage, before, after)| AnswerChange {
+        stage,
+        before,
+        after,
+    })
+    .collect()
+}
+
+/// `part_one,part_two` with `-` for a missing answer, so answers can be
+/// handed to a restarted process on its command line.
+pub fn encode_answers(answers: &Answers) -> String {
+    let encode = |answer: Option<u64>| answer.map_or("-".to_string(), |answer| answer.to_string());
+    format!("{},{}", encode(answers.part_one), encode(answers.part_two))
+}
+
+pub fn decode_answers(text: &str) -> Option<Answers> {
+    let decode = |part: &str| match part {
+        "-" => Some(None),
+        part => part.parse().ok().map(Some),
+    };
+    let (part_one, part_two) = text.split_once(',')?;
+    Some(Answers {
+        part_one: decode(part_one)?,
+        part_two: decode(part_two)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::scratch_dir;
+
+    #[test]
+    fn test_snapshot_changes() {
+        let dir = scratch_dir("watch");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        fs::write(dir.join("target/ignored"), "").unwrap();
+        let input = dir.join("input.txt");
+
+        let roots = [dir.clone(), input.clone()];
+        let before = Snapshot::take(&roots);
+        assert_eq!(before.files().collect::<Vec<_>>(), [dir.join("src/lib.rs")]);
+        assert!(Snapshot::take(&roots).changes_since(&before).is_empty());
+
+        fs::write(&input, "1\n").unwrap();
+        fs::remove_file(dir.join("src/lib.rs")).unwrap();
+        let after = Snapshot::take(&roots);
+        assert_eq!(
+            after.changes_since(&before),
+            [input.clone(), dir.join("src/lib.rs")]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_diff_and_encoding() {
+        let before = Answers {
+            part_one: Some(1),
+            part_two: None,
+        };
+        let after = Answers {
+            part_one: Some(1),
+            part_two: Some(7),
+        };
+        assert_eq!(
+            diff(&before, &after),
+            [AnswerChange {
+                stage: Stage::PartTwo,
+                before: None,
+                after: Some(7)
+            }]
+        );
+        assert!(diff(&after, &after).is_empty());
+
+        assert_eq!(encode_answers(&before), "1,-");
+        assert_eq!(decode_answers("1,-"), Some(before));
+        assert_eq!(decode_answers(&encode_answers(&after)), Some(after));
+        assert_eq!(decode_answers("1"), None);
+        assert_eq!(decode_answers("x,2"), None);
+    }
+}
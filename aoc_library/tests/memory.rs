@@ -0,0 +1,64 @@
+use aoc_library::memory::{self, CountingAllocator, MemoryMeter};
+use aoc_library::solution::{NoMeter, Stage};
+use aoc_library::{Day, Solution};
+use std::sync::Mutex;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// The counters are process-wide, so tests must not allocate concurrently.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+struct Collect;
+
+impl Solution for Collect {
+    const YEAR: u16 = 2000;
+    const DAY: u8 = 2;
+
+    type Input = Vec<u64>;
+
+    fn parse(lines: Vec<String>) -> Vec<u64> {
+        lines.iter().map(|line| line.parse().unwrap()).collect()
+    }
+
+    fn part_one(input: &Vec<u64>) -> Option<u64> {
+        Some(input.iter().sum())
+    }
+
+    fn part_two(input: &Vec<u64>) -> Option<u64> {
+        let doubled: Vec<u64> = input.iter().map(|n| n * 2).collect();
+        Some(doubled.iter().sum())
+    }
+}
+
+#[test]
+fn test_counts_allocations_and_peak() {
+    let _serial = SERIAL.lock().unwrap();
+    let (buffer, usage) = memory::measure(|| {
+        let scratch = vec![0_u8; 10_000];
+        drop(scratch);
+        vec![0_u8; 4_000]
+    });
+    assert!(memory::is_active());
+    assert_eq!(buffer.len(), 4_000);
+    assert!(usage.allocations >= 2);
+    assert!(usage.bytes >= 14_000);
+    assert!(usage.peak >= 10_000);
+    assert!(usage.peak < 14_000 + 1_024);
+}
+
+#[test]
+fn test_memory_meter_reports_each_stage() {
+    let _serial = SERIAL.lock().unwrap();
+    let day = Day::new::<Collect>("unused");
+    let lines: Vec<String> = (0..1_000).map(|n| n.to_string()).collect();
+
+    let mut inner = NoMeter;
+    let mut meter = MemoryMeter::new(&mut inner);
+    let answers = day.solve(&lines, &mut meter);
+
+    assert_eq!(answers.part_one, Some(499_500));
+    assert!(meter.get(Stage::Parse).bytes >= 8_000);
+    assert_eq!(meter.get(Stage::PartOne).allocations, 0);
+    assert!(meter.get(Stage::PartTwo).peak >= 8_000);
+}
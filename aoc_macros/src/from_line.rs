@@ -0,0 +1,122 @@
+//! `#[derive(FromLine)]`.
+
+use crate::item::{self, Item, Kind};
+use crate::pattern::{self, Segment};
+use proc_macro::Literal;
+
+/// The `#[aoc(...)]` settings of one field.
+struct FieldSettings {
+    name: String,
+    separators: Vec<String>,
+}
+
+pub fn expand(item: &Item) -> Result<String, String> {
+    let name = &item.name;
+    if !matches!(item.kind, Kind::Struct) {
+        return Err(format!("{name}: FromLine can only be derived for structs"));
+    }
+
+    let mut pattern = None;
+    if let Some(attr) = item.attr("aoc") {
+        for (key, value) in attr.key_values()? {
+            match key.as_str() {
+                "pattern" => pattern = Some(item::string_value(&value.to_string())?),
+                _ => return Err(format!("{name}: unknown setting {key}, expected pattern")),
+            }
+        }
+    }
+    let pattern = pattern
+        .ok_or_else(|| format!("{name}: FromLine needs #[aoc(pattern = \"...\")] on the struct"))?;
+    let segments = pattern::parse(&pattern)?;
+
+    let mut fields = Vec::new();
+    for field in item.fields()? {
+        let mut separators = Vec::new();
+        for attr in field.attrs.iter().filter(|attr| attr.name == "aoc") {
+            for (key, value) in attr.key_values()? {
+                match key.as_str() {
+                    "sep" => separators.push(item::string_value(&value.to_string())?),
+                    _ => {
+                        return Err(format!(
+                            "{name}.{}: unknown setting {key}, expected sep",
+                            field.name
+                        ))
+                    }
+                }
+            }
+        }
+        fields.push(FieldSettings {
+            name: field.name,
+            separators,
+        });
+    }
+
+    for field in &fields {
+        let uses = segments
+            .iter()
+            .filter(|segment| **segment == Segment::Field(field.name.clone()))
+            .count();
+        if uses != 1 {
+            return Err(format!(
+                "{name}: the pattern must place {{{}}} exactly once",
+                field.name
+            ));
+        }
+    }
+
+    let mut body =
+        String::from("let mut __aoc_cursor = ::aoc_library::parse::Cursor::new(line);\n");
+    for (index, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(text) => {
+                body += &format!("__aoc_cursor.literal({})?;\n", Literal::string(text));
+            }
+            Segment::Field(field) => {
+                let settings = fields
+                    .iter()
+                    .find(|settings| settings.name == *field)
+                    .ok_or_else(|| format!("{name}: the pattern names no field {field}"))?;
+                let until = match segments.get(index + 1) {
+                    Some(Segment::Literal(text)) => {
+                        format!("::core::option::Option::Some({})", Literal::string(text))
+                    }
+                    _ => "::core::option::Option::None".to_string(),
+                };
+                let separators: Vec<String> = settings
+                    .separators
+                    .iter()
+                    .map(|sep| Literal::string(sep).to_string())
+                    .collect();
+                body += &format!(
+                    "let __aoc_field_{field} = __aoc_cursor.field({until}, &[{}])?;\n",
+                    separators.join(", ")
+                );
+            }
+        }
+    }
+    let values: Vec<String> = fields
+        .iter()
+        .map(|field| format!("{0}: __aoc_field_{0}", field.name))
+        .collect();
+    body += &format!(
+        "__aoc_cursor.end()?;\n::core::result::Result::Ok(Self {{ {} }})",
+        values.join(", ")
+    );
+
+    Ok(format!(
+        "impl ::aoc_library::parse::FromLine for {name} {{
+            fn from_line(line: &str) -> ::core::result::Result<Self, ::aoc_library::Error> {{
+                {body}
+            }}
+        }}
+
+        impl ::aoc_library::parse::FromField for {name} {{
+            fn from_field(
+                text: &str,
+                _separators: &[&str],
+            ) -> ::core::result::Result<Self, ::aoc_library::Error> {{
+                <Self as ::aoc_library::parse::FromLine>::from_line(text)
+            }}
+        }}"
+    ))
+}
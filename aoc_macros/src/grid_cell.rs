@@ -0,0 +1,124 @@
+//! `#[derive(GridCell)]`.
+
+use crate::item::{self, Item, Kind};
+use proc_macro::{Literal, TokenTree};
+
+/// The characters of one `#[cell(...)]`: single characters and inclusive
+/// ranges such as `'0'..='9'`.
+fn cell_chars(pieces: Vec<Vec<TokenTree>>) -> Result<Vec<char>, String> {
+    let mut chars = Vec::new();
+    for piece in pieces {
+        match piece.as_slice() {
+            [TokenTree::Literal(c)] => chars.push(item::char_value(&c.to_string())?),
+            [TokenTree::Literal(first), TokenTree::Punct(a), TokenTree::Punct(b), TokenTree::Punct(c), TokenTree::Literal(last)]
+                if [a.as_char(), b.as_char(), c.as_char()] == ['.', '.', '='] =>
+            {
+                let first = item::char_value(&first.to_string())?;
+                let last = item::char_value(&last.to_string())?;
+                if first > last {
+                    return Err(format!("empty range {first:?}..={last:?}"));
+                }
+                chars.extend(first..=last);
+            }
+            _ => return Err("expected characters such as '#' or '0'..='9'".to_string()),
+        }
+    }
+    Ok(chars)
+}
+
+pub fn expand(item: &Item) -> Result<String, String> {
+    let name = &item.name;
+    if !matches!(item.kind, Kind::Enum) {
+        return Err(format!("{name}: GridCell can only be derived for enums"));
+    }
+
+    let mut all_chars = String::new();
+    let mut from_arms = Vec::new();
+    let mut to_arms = Vec::new();
+    for variant in item.variants()? {
+        let path = format!("{name}::{}", variant.name);
+        let attr = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.name == "cell")
+            .ok_or_else(|| format!("{path}: needs #[cell('c')] with its characters"))?;
+        let chars = cell_chars(attr.pieces()).map_err(|error| format!("{path}: {error}"))?;
+        for &c in &chars {
+            if !c.is_ascii() {
+                return Err(format!("{path}: cells must be ASCII, found {c:?}"));
+            }
+            if all_chars.contains(c) {
+                return Err(format!("{path}: {c:?} already belongs to another variant"));
+            }
+            all_chars.push(c);
+        }
+        let Some(&first) = chars.first() else {
+            return Err(format!("{path}: needs at least one character"));
+        };
+
+        let pattern: Vec<String> = chars
+            .iter()
+            .map(|&c| Literal::character(c).to_string())
+            .collect();
+        let pattern = pattern.join(" | ");
+        match variant.tuple_fields {
+            None => {
+                from_arms.push(format!(
+                    "{pattern} => ::core::option::Option::Some(Self::{})",
+                    variant.name
+                ));
+                to_arms.push(format!(
+                    "Self::{} => {}",
+                    variant.name,
+                    Literal::character(first)
+                ));
+            }
+            // The variant keeps which of its characters it was read from.
+            Some(1) => {
+                from_arms.push(format!(
+                    "{pattern} => ::core::option::Option::Some(Self::{}(c))",
+                    variant.name
+                ));
+                to_arms.push(format!("Self::{}(c) => *c", variant.name));
+            }
+            Some(_) => return Err(format!("{path}: a tuple variant holds just its char")),
+        }
+    }
+
+    Ok(format!(
+        "impl ::aoc_library::grid::GridCell for {name} {{
+            const CHARS: &'static str = {chars};
+
+            fn from_char(c: char) -> ::core::option::Option<Self> {{
+                match c {{
+                    {from_arms},
+                    _ => ::core::option::Option::None,
+                }}
+            }}
+
+            fn to_char(&self) -> char {{
+                match self {{
+                    {to_arms},
+                }}
+            }}
+        }}
+
+        impl ::core::convert::TryFrom<char> for {name} {{
+            type Error = ::aoc_library::Error;
+
+            fn try_from(c: char) -> ::core::result::Result<Self, ::aoc_library::Error> {{
+                <Self as ::aoc_library::grid::GridCell>::from_char(c)
+                    .ok_or_else(|| ::aoc_library::grid::unknown_cell::<Self>(c))
+            }}
+        }}
+
+        impl ::core::fmt::Display for {name} {{
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {{
+                ::core::fmt::Display::fmt(&<Self as ::aoc_library::grid::GridCell>::to_char(self), f)
+            }}
+        }}",
+        chars = Literal::string(&all_chars),
+        from_arms = from_arms.join(",\n"),
+        to_arms = to_arms.join(",\n"),
+    ))
+}
@@ -0,0 +1,290 @@
+//! Just enough of Rust's item syntax for the derives: attributes, the
+//! item's name, and its fields or variants. Types are never needed; the
+//! generated code leaves them to inference.
+
+use proc_macro::{Delimiter, Group, Literal, TokenStream, TokenTree};
+use std::iter::Peekable;
+
+/// `#[name(args)]`, with the tokens between the parentheses.
+pub struct Attribute {
+    pub name: String,
+    pub args: Vec<TokenTree>,
+}
+
+pub enum Kind {
+    Struct,
+    Enum,
+}
+
+pub struct Item {
+    pub attrs: Vec<Attribute>,
+    pub kind: Kind,
+    pub name: String,
+    body: Group,
+}
+
+pub struct Field {
+    pub attrs: Vec<Attribute>,
+    pub name: String,
+}
+
+pub struct Variant {
+    pub attrs: Vec<Attribute>,
+    pub name: String,
+    /// The number of fields of a tuple variant, `None` for a unit variant.
+    pub tuple_fields: Option<usize>,
+}
+
+type Tokens = Peekable<std::vec::IntoIter<TokenTree>>;
+
+fn take_attrs(tokens: &mut Tokens) -> Vec<Attribute> {
+    let mut attrs = Vec::new();
+    while matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '#') {
+        tokens.next();
+        let Some(TokenTree::Group(group)) = tokens.next() else {
+            break;
+        };
+        let mut inner = group.stream().into_iter();
+        let name = match inner.next() {
+            Some(TokenTree::Ident(ident)) => ident.to_string(),
+            _ => continue,
+        };
+        let args = match inner.next() {
+            Some(TokenTree::Group(args)) if args.delimiter() == Delimiter::Parenthesis => {
+                args.stream().into_iter().collect()
+            }
+            _ => Vec::new(),
+        };
+        attrs.push(Attribute { name, args });
+    }
+    attrs
+}
+
+fn skip_visibility(tokens: &mut Tokens) {
+    if matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident.to_string() == "pub") {
+        tokens.next();
+        if matches!(tokens.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+        {
+            tokens.next();
+        }
+    }
+}
+
+pub fn parse(input: TokenStream) -> Result<Item, String> {
+    let mut tokens: Tokens = input.into_iter().collect::<Vec<_>>().into_iter().peekable();
+    let attrs = take_attrs(&mut tokens);
+    skip_visibility(&mut tokens);
+    let kind = match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => Kind::Struct,
+        Some(TokenTree::Ident(ident)) if ident.to_string() == "enum" => Kind::Enum,
+        _ => return Err("expected a struct or an enum".to_string()),
+    };
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err("expected the item's name".to_string()),
+    };
+    match tokens.next() {
+        Some(TokenTree::Group(body)) if body.delimiter() == Delimiter::Brace => Ok(Item {
+            attrs,
+            kind,
+            name,
+            body,
+        }),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+            Err(format!("{name}: generic items are not supported"))
+        }
+        _ => Err(format!("{name}: expected named fields in braces")),
+    }
+}
+
+/// Splits on commas outside angle brackets, which token trees do not
+/// group, so `HashMap<K, V>` stays in one piece.
+fn split_commas(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut pieces = vec![Vec::new()];
+    let mut depth = 0_usize;
+    for token in stream {
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                '<' => depth += 1,
+                '>' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    pieces.push(Vec::new());
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        pieces.last_mut().unwrap().push(token);
+    }
+    pieces.retain(|piece| !piece.is_empty());
+    pieces
+}
+
+impl Item {
+    pub fn attr(&self, name: &str) -> Option<&Attribute> {
+        self.attrs.iter().find(|attr| attr.name == name)
+    }
+
+    pub fn fields(&self) -> Result<Vec<Field>, String> {
+        split_commas(self.body.stream())
+            .into_iter()
+            .map(|piece| {
+                let mut tokens: Tokens = piece.into_iter().peekable();
+                let attrs = take_attrs(&mut tokens);
+                skip_visibility(&mut tokens);
+                match (tokens.next(), tokens.next()) {
+                    (Some(TokenTree::Ident(name)), Some(TokenTree::Punct(colon)))
+                        if colon.as_char() == ':' =>
+                    {
+                        Ok(Field {
+                            attrs,
+                            name: name.to_string(),
+                        })
+                    }
+                    _ => Err(format!("{}: expected `name: Type` fields", self.name)),
+                }
+            })
+            .collect()
+    }
+
+    pub fn variants(&self) -> Result<Vec<Variant>, String> {
+        split_commas(self.body.stream())
+            .into_iter()
+            .map(|piece| {
+                let mut tokens: Tokens = piece.into_iter().peekable();
+                let attrs = take_attrs(&mut tokens);
+                let Some(TokenTree::Ident(name)) = tokens.next() else {
+                    return Err(format!("{}: expected variants", self.name));
+                };
+                let tuple_fields = match tokens.next() {
+                    None => None,
+                    Some(TokenTree::Group(group))
+                        if group.delimiter() == Delimiter::Parenthesis =>
+                    {
+                        Some(split_commas(group.stream()).len())
+                    }
+                    Some(_) => {
+                        return Err(format!(
+                            "{}::{name}: expected a unit or tuple variant",
+                            self.name
+                        ))
+                    }
+                };
+                Ok(Variant {
+                    attrs,
+                    name: name.to_string(),
+                    tuple_fields,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Attribute {
+    /// The comma-separated arguments.
+    pub fn pieces(&self) -> Vec<Vec<TokenTree>> {
+        split_commas(self.args.iter().cloned().collect())
+    }
+
+    /// The `key = literal` pairs of the attribute, in order.
+    pub fn key_values(&self) -> Result<Vec<(String, Literal)>, String> {
+        self.pieces()
+            .into_iter()
+            .map(|pair| match pair.as_slice() {
+                [TokenTree::Ident(key), TokenTree::Punct(equals), TokenTree::Literal(value)]
+                    if equals.as_char() == '=' =>
+                {
+                    Ok((key.to_string(), value.clone()))
+                }
+                _ => Err(format!("#[{}]: expected `key = \"value\"`", self.name)),
+            })
+            .collect()
+    }
+}
+
+/// The value of a string literal token, e.g. `"a\"b"` or `r#"a"b"#`.
+pub fn string_value(literal: &str) -> Result<String, String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = &raw[hashes..raw.len() - hashes];
+        return body
+            .strip_prefix('"')
+            .and_then(|body| body.strip_suffix('"'))
+            .map(str::to_string)
+            .ok_or_else(|| format!("expected a string literal, found {literal}"));
+    }
+    let body = literal
+        .strip_prefix('"')
+        .and_then(|body| body.strip_suffix('"'))
+        .ok_or_else(|| format!("expected a string literal, found {literal}"))?;
+    unescape(body)
+}
+
+/// The value of a character literal token, e.g. `'#'` or `'\''`.
+pub fn char_value(literal: &str) -> Result<char, String> {
+    let body = literal
+        .strip_prefix('\'')
+        .and_then(|body| body.strip_suffix('\''))
+        .ok_or_else(|| format!("expected a character literal, found {literal}"))?;
+    let value = unescape(body)?;
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(format!("expected a character literal, found {literal}")),
+    }
+}
+
+fn unescape(body: &str) -> Result<String, String> {
+    let mut value = String::new();
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('t') => value.push('\t'),
+            Some('r') => value.push('\r'),
+            Some('0') => value.push('\0'),
+            Some(c @ ('\\' | '"' | '\'')) => value.push(c),
+            Some('u') => {
+                let code: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                let c = u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid escape \\u{{{code}}}"))?;
+                value.push(c);
+            }
+            Some('x') => {
+                let code: String = chars.by_ref().take(2).collect();
+                let c = u8::from_str_radix(&code, 16)
+                    .map_err(|_| format!("invalid escape \\x{code}"))?;
+                value.push(char::from(c));
+            }
+            other => return Err(format!("unsupported escape \\{}", other.unwrap_or(' '))),
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal_values() {
+        assert_eq!(
+            string_value(r#""Card {id}: {n}""#).unwrap(),
+            "Card {id}: {n}"
+        );
+        assert_eq!(string_value(r#""a\"b\\c\n""#).unwrap(), "a\"b\\c\n");
+        assert_eq!(string_value(r###"r#"a"b"#"###).unwrap(), "a\"b");
+        assert_eq!(string_value(r#""\u{2603}""#).unwrap(), "\u{2603}");
+        assert!(string_value("12").is_err());
+
+        assert_eq!(char_value("'#'").unwrap(), '#');
+        assert_eq!(char_value(r"'\''").unwrap(), '\'');
+        assert!(char_value("'ab'").is_err());
+    }
+}
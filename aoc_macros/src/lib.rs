@@ -0,0 +1,60 @@
+//! Derive macros for `aoc_library`, re-exported from there; use them
+//! through the library rather than depending on this crate.
+//!
+//! The macros parse their input with `proc_macro` alone and generate code
+//! as text, so the workspace needs no crates from outside it.
+
+mod from_line;
+mod grid_cell;
+mod item;
+mod pattern;
+
+use proc_macro::{Literal, TokenStream};
+
+/// Implements `aoc_library::parse::FromLine` from a pattern on the struct:
+///
+/// ```ignore
+/// #[derive(FromLine)]
+/// #[aoc(pattern = "Card {id}: {numbers} | {winning}")]
+/// struct Card {
+///     id: u32,
+///     numbers: Vec<u32>,
+///     #[aoc(sep = ",")]
+///     winning: Vec<u32>,
+/// }
+/// ```
+///
+/// See `aoc_library::parse` for how fields are matched.
+#[proc_macro_derive(FromLine, attributes(aoc))]
+pub fn derive_from_line(input: TokenStream) -> TokenStream {
+    expand(item::parse(input).and_then(|item| from_line::expand(&item)))
+}
+
+/// Implements `aoc_library::grid::GridCell`, `TryFrom<char>` and `Display`
+/// for an enum whose variants name their characters:
+///
+/// ```ignore
+/// #[derive(GridCell)]
+/// enum Tile {
+///     #[cell('.')]
+///     Open,
+///     #[cell('#')]
+///     Wall,
+///     #[cell('0'..='9')]
+///     Marker(char),
+/// }
+/// ```
+///
+/// A unit variant displays as its first character; a variant holding a
+/// `char` keeps the one it was read from.
+#[proc_macro_derive(GridCell, attributes(cell))]
+pub fn derive_grid_cell(input: TokenStream) -> TokenStream {
+    expand(item::parse(input).and_then(|item| grid_cell::expand(&item)))
+}
+
+fn expand(code: Result<String, String>) -> TokenStream {
+    let code = code.unwrap_or_else(|message| {
+        format!("::core::compile_error!({});", Literal::string(&message))
+    });
+    code.parse().expect("generated code is valid Rust")
+}
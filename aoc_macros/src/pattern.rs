@@ -0,0 +1,94 @@
+//! Line patterns such as `"Card {id}: {numbers} | {winning}"`: literal
+//! text with `{field}` placeholders, and `{{` and `}}` for braces.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+pub fn parse(pattern: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed {{ in pattern {pattern:?}")),
+                    }
+                }
+                let name = name.trim();
+                if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    return Err(format!("invalid placeholder {{{name}}} in {pattern:?}"));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                if matches!(segments.last(), Some(Segment::Field(_))) {
+                    return Err(format!(
+                        "placeholders need text between them in {pattern:?}"
+                    ));
+                }
+                segments.push(Segment::Field(name.to_string()));
+            }
+            '}' => {
+                return Err(format!(
+                    "unmatched }} in {pattern:?}; write }}}} for a brace"
+                ))
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pattern() {
+        assert_eq!(
+            parse("Card {id}: {numbers} | {winning}").unwrap(),
+            [
+                Segment::Literal("Card ".into()),
+                Segment::Field("id".into()),
+                Segment::Literal(": ".into()),
+                Segment::Field("numbers".into()),
+                Segment::Literal(" | ".into()),
+                Segment::Field("winning".into()),
+            ]
+        );
+        assert_eq!(
+            parse("{{{x}}}").unwrap(),
+            [
+                Segment::Literal("{".into()),
+                Segment::Field("x".into()),
+                Segment::Literal("}".into()),
+            ]
+        );
+        assert!(parse("{a}{b}").is_err());
+        assert!(parse("{a b}").is_err());
+        assert!(parse("a}").is_err());
+        assert_eq!(
+            parse("Card {id").unwrap_err(),
+            "unclosed { in pattern \"Card {id\""
+        );
+    }
+}